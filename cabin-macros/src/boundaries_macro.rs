@@ -14,6 +14,16 @@ pub fn boundaries_macro() -> TokenStream {
             #[::cabin::private::linkme::distributed_slice]
             #[linkme(crate = ::cabin::private::linkme)]
             pub static #ident: [fn(&mut ::cabin::boundary_registry::BoundaryRegistry)] = [..];
+
+            /// Ids are not checked for collisions at compile time, so check all ids of the
+            /// linked crates as part of the crate's tests.
+            #[test]
+            #[cfg(test)]
+            fn unique_ids() {
+                if let Err(err) = ::cabin::id::check_unique() {
+                    panic!("{err}");
+                }
+            }
         }
         #[cfg(not(target_arch = "wasm32"))]
         pub use __boundaries::#ident as BOUNDARIES;
@@ -1,15 +1,97 @@
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::token::Comma;
-use syn::{Error, FnArg, ItemFn, Pat, PatType, Signature, Type};
+use syn::token::{Comma, Paren};
+use syn::{
+    Error, FnArg, ItemFn, LitInt, Pat, PatType, Path, Signature, Token, Type, parenthesized,
+};
+
+mod kw {
+    syn::custom_keyword!(reduce);
+    syn::custom_keyword!(version);
+    syn::custom_keyword!(migrate);
+    syn::custom_keyword!(server_state);
+}
+
+pub struct BoundaryArgs {
+    events: Punctuated<Type, Comma>,
+    /// Whether the events are reduced into the boundary's state (`#[boundary(reduce(A, B))]`)
+    /// instead of being handled by the boundary's body.
+    reduce: bool,
+    /// Schema version of the boundary's state (`#[boundary(version = 2)]`).
+    version: Option<LitInt>,
+    /// Function to migrate state of older versions (`#[boundary(migrate = path::to::fn)]`).
+    migrate: Option<Path>,
+    /// Whether the state is kept on the server (`#[boundary(server_state)]`).
+    server_state: bool,
+}
+
+impl Parse for BoundaryArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = BoundaryArgs {
+            events: Punctuated::new(),
+            reduce: false,
+            version: None,
+            migrate: None,
+            server_state: false,
+        };
+
+        while !input.is_empty() {
+            if input.peek(kw::reduce) && input.peek2(Paren) {
+                let span = input.parse::<kw::reduce>()?.span;
+                if args.reduce || !args.events.is_empty() {
+                    return Err(Error::new(
+                        span,
+                        "`reduce(..)` cannot be combined with other events",
+                    ));
+                }
+                let content;
+                parenthesized!(content in input);
+                args.events = content.parse_terminated(Type::parse, Comma)?;
+                args.reduce = true;
+            } else if input.peek(kw::version) && input.peek2(Token![=]) {
+                input.parse::<kw::version>()?;
+                input.parse::<Token![=]>()?;
+                args.version = Some(input.parse()?);
+            } else if input.peek(kw::migrate) && input.peek2(Token![=]) {
+                input.parse::<kw::migrate>()?;
+                input.parse::<Token![=]>()?;
+                args.migrate = Some(input.parse()?);
+            } else if input.peek(kw::server_state)
+                && (input.peek2(Comma) || input.peek2(syn::parse::End))
+            {
+                input.parse::<kw::server_state>()?;
+                args.server_state = true;
+            } else {
+                if args.reduce {
+                    return Err(input.error("`reduce(..)` cannot be combined with other events"));
+                }
+                args.events.push(input.parse()?);
+            }
+
+            if !input.is_empty() {
+                input.parse::<Comma>()?;
+            }
+        }
+
+        Ok(args)
+    }
+}
 
 pub fn boundary_attribute(
     item: ItemFn,
-    events: Punctuated<Type, Comma>,
+    args: BoundaryArgs,
     wasm_enabled: bool,
 ) -> syn::Result<TokenStream> {
+    let BoundaryArgs {
+        events,
+        reduce,
+        version,
+        migrate,
+        server_state,
+    } = args;
     let ItemFn {
         attrs,
         vis,
@@ -77,6 +159,77 @@ pub fn boundary_attribute(
         quote! {}
     };
     let events = events.into_iter().collect::<Vec<Type>>();
+    let version = version.map(|version| quote! { .with_version(#version) });
+    let migrate = migrate.map(|migrate| quote! { .with_migration(#migrate) });
+    if server_state && wasm_enabled {
+        return Err(Error::new(
+            ident.span(),
+            "server-side state is not supported for wasm boundaries",
+        ));
+    }
+    let server_state = server_state.then(|| quote! { .with_server_state() });
+    // Wasm boundaries are looked up by their export name, which thus must be their id.
+    let id = if wasm_enabled {
+        quote! {
+            const ID: &str = FULL_PATH;
+        }
+    } else {
+        quote! {
+            const COMPACT: [u8; ::cabin::id::COMPACT_LEN] = ::cabin::id::compact(FULL_PATH);
+            const ID: &str = ::cabin::id::select(FULL_PATH, &COMPACT);
+        }
+    };
+
+    // For reducer-style boundaries, apply the event (if any) to the state before the body is
+    // executed. Declared events without a `Reducer` implementation fail to compile. The body only
+    // sees `event` and `take_event` functions that are restricted to the declared events.
+    let (inner_inputs, block) = if reduce {
+        if args_idents.len() != 1 {
+            return Err(Error::new(
+                inputs.span(),
+                "reducer boundaries must have exactly one (state) argument",
+            ));
+        }
+        let state_ident = &args_idents[0];
+        let state_type = &args_types[0];
+        (
+            &inputs_no_mut,
+            quote! {{
+                enum Declared {}
+                #(impl ::cabin::view::boundary::internal::DeclaredEvent<Declared> for #events {})*
+
+                #[allow(dead_code)]
+                fn event<E>() -> ::std::option::Option<E>
+                where
+                    E: ::cabin::view::boundary::internal::DeclaredEvent<Declared> + Copy,
+                {
+                    ::cabin::scope::event::<E>()
+                }
+
+                #[allow(dead_code)]
+                fn take_event<E>() -> ::std::option::Option<E>
+                where
+                    E: ::cabin::view::boundary::internal::DeclaredEvent<Declared>,
+                {
+                    ::cabin::scope::take_event::<E>()
+                }
+
+                #[allow(unused_mut)]
+                let mut #state_ident: #state_type = {
+                    let mut state = #state_ident;
+                    #(
+                        if let Some(event) = ::cabin::scope::take_event::<#events>() {
+                            state = ::cabin::view::boundary::Reducer::<#events>::update(state, event);
+                        }
+                    )*
+                    state
+                };
+                #block
+            }},
+        )
+    } else {
+        (&inputs, quote! { #block })
+    };
 
     let wasm = wasm_enabled.then(|| {
         quote! {
@@ -91,18 +244,21 @@ pub fn boundary_attribute(
     Ok(quote! {
         #(#attrs)*
         #vis #constness #asyncness #unsafety #abi fn #ident #generics(#inputs_no_mut #variadic) #output {
-            #vis #constness #asyncness #unsafety #abi fn #inner_ident #generics(#inputs #variadic) #output {
+            #vis #constness #asyncness #unsafety #abi fn #inner_ident #generics(#inner_inputs #variadic) #output
                 #block
-            }
 
-            const ID: &str = concat!(module_path!(), "::", #name);
+            const FULL_PATH: &str = concat!(module_path!(), "::", #name);
+            #id
             static EVENTS: &'static [&'static str] = &[#(::cabin::event::event_id::<#events>(),)*];
             static BOUNDARY: ::cabin::view::boundary::BoundaryRef<(#args_types)> =
                 ::cabin::view::boundary::BoundaryRef::new(
                     ID,
                     &EVENTS,
                     &(move |(#args_idents)| Box::pin(#to_async)),
-                );
+                )
+                #version
+                #migrate
+                #server_state;
 
             #[cfg(not(target_arch = "wasm32"))]
             #[::cabin::private::linkme::distributed_slice(crate::BOUNDARIES)]
@@ -111,6 +267,15 @@ pub fn boundary_attribute(
                 r.register(&BOUNDARY)
             }
 
+            #[cfg(not(target_arch = "wasm32"))]
+            #[::cabin::private::linkme::distributed_slice(::cabin::id::IDS)]
+            #[linkme(crate = ::cabin::private::linkme)]
+            static __ID: ::cabin::id::Id = ::cabin::id::Id {
+                kind: ::cabin::id::IdKind::Boundary,
+                id: ID,
+                full_path: FULL_PATH,
+            };
+
             #wasm
 
             ::cabin::view::boundary::internal::Boundary::upgrade(
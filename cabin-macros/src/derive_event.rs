@@ -19,7 +19,22 @@ pub fn derive_event(input: DeriveInput) -> syn::Result<TokenStream> {
     Ok(quote! {
         #[automatically_derived]
         impl ::cabin::event::Event for #ident {
-            const ID: &'static str =  concat!(module_path!(), "::", #name);
+            const ID: &'static str = {
+                const FULL_PATH: &str = concat!(module_path!(), "::", #name);
+                const COMPACT: [u8; ::cabin::id::COMPACT_LEN] = ::cabin::id::compact(FULL_PATH);
+                ::cabin::id::select(FULL_PATH, &COMPACT)
+            };
         }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        const _: () = {
+            #[::cabin::private::linkme::distributed_slice(::cabin::id::IDS)]
+            #[linkme(crate = ::cabin::private::linkme)]
+            static ID: ::cabin::id::Id = ::cabin::id::Id {
+                kind: ::cabin::id::IdKind::Event,
+                id: <#ident as ::cabin::event::Event>::ID,
+                full_path: concat!(module_path!(), "::", #name),
+            };
+        };
     })
 }
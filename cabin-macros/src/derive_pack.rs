@@ -0,0 +1,182 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataStruct, DeriveInput, Error, Expr, Fields, Ident, Member, Type, token,
+};
+
+/// Derives `Pack` for the common case of packing a value to its primary key, e.g.:
+///
+/// ```rust,ignore
+/// #[derive(Clone, Pack)]
+/// #[pack(conn = Db, error = DbError, unpack = User::find, unpack_many = User::find_many)]
+/// struct User {
+///     #[pack(key)]
+///     id: UserId,
+///     name: String,
+/// }
+/// ```
+///
+/// The key field defaults to a field named `id`, `unpack_many` is optional.
+pub fn derive_pack(input: DeriveInput) -> syn::Result<TokenStream> {
+    let DeriveInput {
+        attrs,
+        vis: _,
+        ident,
+        generics,
+        data,
+    } = input;
+
+    if !generics.params.is_empty() {
+        return Err(Error::new(
+            ident.span(),
+            "Pack cannot be derived with generics",
+        ));
+    }
+
+    let opts = extract_options(&attrs)?;
+    let Some(conn) = opts.conn else {
+        return Err(Error::new(
+            ident.span(),
+            "missing connection type, add `#[pack(conn = ..)]`",
+        ));
+    };
+    let Some(error) = opts.error else {
+        return Err(Error::new(
+            ident.span(),
+            "missing error type, add `#[pack(error = ..)]`",
+        ));
+    };
+    let Some(unpack) = opts.unpack else {
+        return Err(Error::new(
+            ident.span(),
+            "missing unpack function, add `#[pack(unpack = ..)]`",
+        ));
+    };
+
+    let Data::Struct(DataStruct { fields, .. }) = data else {
+        return Err(Error::new(
+            ident.span(),
+            "Pack can only be derived from structs",
+        ));
+    };
+    let (key, key_type) = find_key(&ident, &fields)?;
+
+    let unpack_many = opts.unpack_many.map(|unpack_many| {
+        quote! {
+            fn unpack_many(
+                packs: ::std::vec::Vec<#key_type>,
+                conn: &#conn,
+            ) -> impl ::std::future::Future<
+                Output = ::std::vec::Vec<::std::result::Result<Self, #error>>
+            > {
+                #unpack_many(packs, conn)
+            }
+        }
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl ::cabin::pack::Pack<#conn> for #ident {
+            type Packed = #key_type;
+            type Error = #error;
+
+            fn pack(&self) -> #key_type {
+                ::std::clone::Clone::clone(&self.#key)
+            }
+
+            fn unpack(
+                pack: #key_type,
+                conn: &#conn,
+            ) -> impl ::std::future::Future<Output = ::std::result::Result<Self, #error>> {
+                #unpack(pack, conn)
+            }
+
+            #unpack_many
+        }
+    })
+}
+
+fn find_key<'a>(ident: &Ident, fields: &'a Fields) -> syn::Result<(Member, &'a Type)> {
+    let mut key = None;
+    let mut id = None;
+    for (i, field) in fields.iter().enumerate() {
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(i.into()),
+        };
+        if field.attrs.iter().any(|a| a.path().is_ident("pack")) {
+            for attr in field.attrs.iter().filter(|a| a.path().is_ident("pack")) {
+                let opt: Ident = attr.parse_args()?;
+                if opt != "key" {
+                    return Err(Error::new(opt.span(), "unknown field option"));
+                }
+            }
+            if key.is_some() {
+                return Err(Error::new(field.span(), "only one field can be the key"));
+            }
+            key = Some((member, &field.ty));
+        } else if field.ident.as_ref().is_some_and(|ident| ident == "id") {
+            id = Some((member, &field.ty));
+        }
+    }
+
+    key.or(id).ok_or_else(|| {
+        Error::new(
+            ident.span(),
+            "missing key field, add `#[pack(key)]` to the primary key field",
+        )
+    })
+}
+
+#[derive(Default)]
+struct Opts {
+    conn: Option<Type>,
+    error: Option<Type>,
+    unpack: Option<Expr>,
+    unpack_many: Option<Expr>,
+}
+
+fn extract_options(attrs: &[Attribute]) -> syn::Result<Opts> {
+    let mut opts = Opts::default();
+
+    for attr in attrs.iter().filter(|a| a.path().is_ident("pack")) {
+        for opt in attr.parse_args_with(Punctuated::<OptionExpr, token::Comma>::parse_terminated)? {
+            match opt {
+                OptionExpr::Conn(ty) => opts.conn = Some(ty),
+                OptionExpr::Error(ty) => opts.error = Some(ty),
+                OptionExpr::Unpack(expr) => opts.unpack = Some(expr),
+                OptionExpr::UnpackMany(expr) => opts.unpack_many = Some(expr),
+            }
+        }
+    }
+
+    Ok(opts)
+}
+
+enum OptionExpr {
+    Conn(Type),
+    Error(Type),
+    Unpack(Expr),
+    UnpackMany(Expr),
+}
+
+impl Parse for OptionExpr {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let key = Ident::parse(input)?;
+        token::Eq::parse(input)?;
+        if key == "conn" {
+            Ok(OptionExpr::Conn(input.parse()?))
+        } else if key == "error" {
+            Ok(OptionExpr::Error(input.parse()?))
+        } else if key == "unpack" {
+            Ok(OptionExpr::Unpack(input.parse()?))
+        } else if key == "unpack_many" {
+            Ok(OptionExpr::UnpackMany(input.parse()?))
+        } else {
+            Err(Error::new(key.span(), "unknown pack option"))
+        }
+    }
+}
@@ -2,13 +2,12 @@ mod boundaries_macro;
 mod boundary_attribute;
 mod derive_attribute;
 mod derive_event;
+mod derive_pack;
 mod length_aliases_attribute;
 mod view_macro_attribute;
 
 use proc_macro::TokenStream;
-use syn::punctuated::Punctuated;
-use syn::token::Comma;
-use syn::{DeriveInput, ItemFn, ItemTrait, Type, parse_macro_input};
+use syn::{DeriveInput, ItemFn, ItemTrait, parse_macro_input};
 
 #[proc_macro_derive(Attribute, attributes(attribute))]
 pub fn derive_attribute(item: TokenStream) -> TokenStream {
@@ -28,11 +27,20 @@ pub fn derive_event(item: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(Pack, attributes(pack))]
+pub fn derive_pack(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    match derive_pack::derive_pack(input) {
+        Ok(ts) => ts.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 #[proc_macro_attribute]
 pub fn boundary(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let events = parse_macro_input!(attr with Punctuated::<Type, Comma>::parse_terminated);
+    let args = parse_macro_input!(attr as boundary_attribute::BoundaryArgs);
     let input = parse_macro_input!(item as ItemFn);
-    match boundary_attribute::boundary_attribute(input, events, false) {
+    match boundary_attribute::boundary_attribute(input, args, false) {
         Ok(ts) => ts.into(),
         Err(err) => err.into_compile_error().into(),
     }
@@ -57,9 +65,9 @@ pub fn length_aliases(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 #[proc_macro_attribute]
 pub fn wasm_boundary(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let events = parse_macro_input!(attr with Punctuated::<Type, Comma>::parse_terminated);
+    let args = parse_macro_input!(attr as boundary_attribute::BoundaryArgs);
     let input = parse_macro_input!(item as ItemFn);
-    match boundary_attribute::boundary_attribute(input, events, true) {
+    match boundary_attribute::boundary_attribute(input, args, true) {
         Ok(ts) => ts.into(),
         Err(err) => err.into_compile_error().into(),
     }
@@ -75,6 +83,11 @@ pub fn view_macro(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 }
 
+/// Collects all boundaries of the crate. Call it once in the crate that runs the server.
+///
+/// Also generates a test (`__boundaries::unique_ids`) that checks the ids of all events and
+/// boundaries of the linked crates for collisions (see `cabin::id::check_unique`). Collisions are
+/// not detected at compile time, so run the crate's tests in CI.
 #[proc_macro]
 #[allow(non_snake_case)]
 pub fn BOUNDARIES(item: TokenStream) -> TokenStream {
@@ -6,7 +6,7 @@ use std::task::{Context, Poll};
 
 use bytes::Bytes;
 use cabin::boundary_registry::BoundaryRegistry;
-use http::{Method, Request, Response};
+use http::{Method, Request, Response, header};
 use tower_layer::Layer;
 use tower_service::Service;
 
@@ -84,6 +84,15 @@ where
                 let res = registry.handle(&id, req).await;
                 let (parts, body) = res.into_parts();
                 Ok(Response::from_parts(parts, body.into()))
+            } else if cfg!(debug_assertions)
+                && req.method() == Method::GET
+                && req.uri().path() == "/__cabin/ids"
+            {
+                // Mapping of (short) ids to the full paths of events and boundaries
+                Ok(Response::builder()
+                    .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                    .body(cabin::id::mapping_table().into())
+                    .unwrap())
             } else {
                 service.call(req).await
             }
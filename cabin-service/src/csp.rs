@@ -0,0 +1,305 @@
+use std::borrow::Cow;
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use cabin::csp::{Nonce, with_nonce};
+use http::{HeaderValue, Request, Response, StatusCode, header};
+use tower_layer::Layer;
+use tower_service::Service;
+
+pub fn layer(policy: ContentSecurityPolicy) -> CspLayer {
+    CspLayer {
+        policy: Arc::new(policy),
+    }
+}
+
+/// Layer that generates a nonce for each request, applies it to all scripts and styles rendered by
+/// cabin, and sets the `Content-Security-Policy` header accordingly.
+#[derive(Clone)]
+pub struct CspLayer {
+    policy: Arc<ContentSecurityPolicy>,
+}
+
+/// Service that generates a nonce for each request, applies it to all scripts and styles rendered
+/// by cabin, and sets the `Content-Security-Policy` header accordingly.
+#[derive(Clone)]
+pub struct CspService<S> {
+    policy: Arc<ContentSecurityPolicy>,
+    service: S,
+}
+
+/// Builder for a `Content-Security-Policy` header. The request's nonce is automatically added to
+/// the `script-src` and `style-src` directives.
+#[derive(Debug, Clone)]
+pub struct ContentSecurityPolicy {
+    directives: Vec<(Cow<'static, str>, Vec<Cow<'static, str>>)>,
+    report_only: bool,
+}
+
+impl ContentSecurityPolicy {
+    /// A strict policy that only allows resources from the same origin, and only scripts and
+    /// styles emitted by cabin (or manually tagged with [cabin::scope::nonce]).
+    pub fn new() -> Self {
+        Self::empty()
+            .default_src(["'self'"])
+            .script_src(["'self'"])
+            .style_src(["'self'"])
+            .object_src(["'none'"])
+            .base_uri(["'self'"])
+    }
+
+    /// A policy without any directives.
+    pub fn empty() -> Self {
+        Self {
+            directives: Vec::new(),
+            report_only: false,
+        }
+    }
+
+    /// Set the sources of the given directive (replaces previously set sources).
+    pub fn directive(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        let name = name.into();
+        let sources = sources.into_iter().map(Into::into).collect();
+        if let Some((_, existing)) = self.directives.iter_mut().find(|(n, _)| *n == name) {
+            *existing = sources;
+        } else {
+            self.directives.push((name, sources));
+        }
+        self
+    }
+
+    /// Fallback for all other fetch directives.
+    pub fn default_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("default-src", sources)
+    }
+
+    /// Valid sources for scripts (the request's nonce is added automatically).
+    pub fn script_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("script-src", sources)
+    }
+
+    /// Valid sources for styles (the request's nonce is added automatically).
+    pub fn style_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("style-src", sources)
+    }
+
+    /// Valid sources for images and favicons.
+    pub fn img_src(self, sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self {
+        self.directive("img-src", sources)
+    }
+
+    /// Valid sources for fonts.
+    pub fn font_src(self, sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self {
+        self.directive("font-src", sources)
+    }
+
+    /// Valid targets for `fetch`, `EventSource` and `WebSocket` connections.
+    pub fn connect_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("connect-src", sources)
+    }
+
+    /// Valid sources for nested browsing contexts (e.g. `iframe`).
+    pub fn frame_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("frame-src", sources)
+    }
+
+    /// Valid sources for `object` and `embed` elements.
+    pub fn object_src(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("object-src", sources)
+    }
+
+    /// Valid URLs for the document's `base` element.
+    pub fn base_uri(self, sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>) -> Self {
+        self.directive("base-uri", sources)
+    }
+
+    /// Valid targets for form submissions.
+    pub fn form_action(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("form-action", sources)
+    }
+
+    /// Valid parents that may embed the page.
+    pub fn frame_ancestors(
+        self,
+        sources: impl IntoIterator<Item = impl Into<Cow<'static, str>>>,
+    ) -> Self {
+        self.directive("frame-ancestors", sources)
+    }
+
+    /// Only report violations instead of enforcing the policy
+    /// (`Content-Security-Policy-Report-Only`).
+    pub fn report_only(mut self, report_only: bool) -> Self {
+        self.report_only = report_only;
+        self
+    }
+
+    /// The header value for the given `nonce`. The nonce is appended to the `script-src` and
+    /// `style-src` directives. If one of them is missing, it is added with the sources of
+    /// `default-src` (which it would otherwise fall back to) plus the nonce.
+    pub fn header_value(&self, nonce: &Nonce) -> String {
+        let default_src = self
+            .directives
+            .iter()
+            .find(|(name, _)| name == "default-src")
+            .map(|(_, sources)| sources);
+        let missing = ["script-src", "style-src"]
+            .into_iter()
+            .filter(|name| !self.directives.iter().any(|(n, _)| n == name))
+            .filter_map(|name| Some((Cow::Borrowed(name), default_src?)));
+
+        let mut value = String::new();
+        let directives = self
+            .directives
+            .iter()
+            .map(|(name, sources)| (name.clone(), sources));
+        for (i, (name, sources)) in directives.chain(missing).enumerate() {
+            if i > 0 {
+                value += "; ";
+            }
+            value += &name;
+            for source in sources {
+                value += " ";
+                value += source;
+            }
+            if matches!(name.as_ref(), "script-src" | "style-src") {
+                value += " 'nonce-";
+                value += nonce.as_str();
+                value += "'";
+            }
+        }
+        value
+    }
+}
+
+impl Default for ContentSecurityPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for CspLayer {
+    type Service = CspService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CspService {
+            policy: Arc::clone(&self.policy),
+            service: inner,
+        }
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for CspService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: std::marker::Send,
+    ReqBody: http_body::Body<Data = Bytes> + Send + 'static,
+    ResBody: http_body::Body<Data = Bytes> + Default,
+{
+    type Response = Response<ResBody>;
+    type Error = Infallible;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let policy = Arc::clone(&self.policy);
+        let mut service = self.service.clone();
+        Box::pin(async move {
+            let nonce = match Nonce::generate() {
+                Ok(nonce) => nonce,
+                Err(err) => {
+                    tracing::error!(%err, "failed to generate csp nonce");
+                    return Ok(Response::builder()
+                        .status(StatusCode::INTERNAL_SERVER_ERROR)
+                        .body(Default::default())
+                        .unwrap());
+                }
+            };
+
+            let mut res = with_nonce(nonce.clone(), service.call(req)).await?;
+
+            let name = if policy.report_only {
+                header::CONTENT_SECURITY_POLICY_REPORT_ONLY
+            } else {
+                header::CONTENT_SECURITY_POLICY
+            };
+            if !res.headers().contains_key(&name) {
+                match HeaderValue::from_str(&policy.header_value(&nonce)) {
+                    Ok(value) => {
+                        res.headers_mut().insert(name, value);
+                    }
+                    Err(err) => {
+                        tracing::error!(%err, "invalid content security policy header value");
+                    }
+                }
+            }
+
+            Ok(res)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_value() {
+        let nonce = Nonce::generate().unwrap();
+        let n = nonce.as_str();
+
+        assert_eq!(
+            ContentSecurityPolicy::new().header_value(&nonce),
+            format!(
+                "default-src 'self'; script-src 'self' 'nonce-{n}'; style-src 'self' 'nonce-{n}'; \
+                 object-src 'none'; base-uri 'self'"
+            )
+        );
+        assert_eq!(
+            ContentSecurityPolicy::empty()
+                .default_src(["'self'"])
+                .style_src(["'self'", "https://fonts.example.com"])
+                .header_value(&nonce),
+            format!(
+                "default-src 'self'; style-src 'self' https://fonts.example.com 'nonce-{n}'; \
+                 script-src 'self' 'nonce-{n}'"
+            )
+        );
+        assert_eq!(ContentSecurityPolicy::empty().header_value(&nonce), "");
+    }
+}
@@ -1,5 +1,6 @@
 pub mod assets;
 pub mod boundaries;
+pub mod csp;
 #[cfg(feature = "livereload")]
 pub mod livereload;
 pub mod redirects;
@@ -3,46 +3,76 @@ use std::time::{Duration, Instant};
 
 use cabin::basic_document;
 use cabin::prelude::*;
+use cabin::scope::{event, memo, spawn, spawn_blocking};
 use cabin::view::FutureExt;
 use http::Request;
 use tokio::net::TcpListener;
 
 async fn app() -> impl View {
-    let start = Instant::now();
+    let start = request_start().await;
     basic_document(h::div![
         h::ul![
-            delayed(start, Duration::from_secs(1)).into_any_view(),
-            delayed(start, Duration::from_secs(2)).into_any_view(),
-            delayed(start, Duration::from_secs(3)).into_any_view(),
+            delayed(Duration::from_secs(1)).into_any_view(),
+            delayed(Duration::from_secs(2)).into_any_view(),
+            delayed(Duration::from_secs(3)).into_any_view(),
+            blocking(Duration::from_secs(2)).into_any_view(),
         ]
         .into_any_view()
         .await,
         h::text!("page finished after {:.2}", start.elapsed().as_secs_f64()),
+        h::button("reload").on_click(()),
     ])
 }
 
-async fn delayed(start: Instant, delay: Duration) -> impl View {
+/// The time the current request started, shared by all views of the request.
+async fn request_start() -> Instant {
+    memo("request_start", || async { Instant::now() }).await
+}
+
+async fn delayed(delay: Duration) -> impl View {
+    let start = request_start().await;
     let started_at = start.elapsed();
-    let inner = Instant::now();
-    tokio::time::sleep(delay).await;
-    let task_local = TASK_LOCAL
-        .try_with(|_| "task local works")
-        .unwrap_or("task local DOES NOT work");
+    // Tasks spawned via `cabin::scope::spawn` (unlike `tokio::spawn`) keep access to the scope of
+    // the request, e.g. to its events and memos.
+    let (took, same_request) = spawn(async move {
+        let inner = Instant::now();
+        tokio::time::sleep(delay).await;
+        (inner.elapsed(), request_start().await == start)
+    })
+    .await
+    .unwrap();
     h::li(h::text!(
-        "delayed for {:?}, started after {:.2}, took {:.2}, finished after {:.2} -- {task_local}",
+        "delayed for {:?}, started after {:.2}, took {:.2}, finished after {:.2} -- {}",
         delay,
         started_at.as_secs_f64(),
-        inner.elapsed().as_secs_f64(),
+        took.as_secs_f64(),
         start.elapsed().as_secs_f64(),
+        if same_request {
+            "spawned task shares the request scope"
+        } else {
+            "spawned task DOES NOT share the request scope"
+        },
     ))
 }
 
-cabin::BOUNDARIES!();
-
-tokio::task_local! {
-    static TASK_LOCAL: &'static str;
+async fn blocking(delay: Duration) -> impl View {
+    let start = request_start().await;
+    // The same applies to blocking code run via `cabin::scope::spawn_blocking`.
+    let reloaded = spawn_blocking(move || {
+        std::thread::sleep(delay);
+        event::<()>().is_some()
+    })
+    .await
+    .unwrap();
+    h::li(h::text!(
+        "blocked for {:?}, finished after {:.2} -- reload clicked: {reloaded}",
+        delay,
+        start.elapsed().as_secs_f64(),
+    ))
 }
 
+cabin::BOUNDARIES!();
+
 #[tokio::main]
 async fn main() {
     let filter =
@@ -57,11 +87,8 @@ async fn main() {
     let server = axum::Router::new()
         .route(
             "/",
-            axum::routing::get(|| TASK_LOCAL.scope("task local works", cabin::get_page(app))).put(
-                |req: Request<axum::body::Body>| {
-                    cabin::put_page(req, || TASK_LOCAL.scope("task local works", app()))
-                },
-            ),
+            axum::routing::get(|| cabin::get_page(app))
+                .put(|req: Request<axum::body::Body>| cabin::put_page(req, app)),
         )
         .layer(cabin_service::redirects::layer())
         .layer(cabin_service::boundaries::layer(&BOUNDARIES))
@@ -0,0 +1,70 @@
+use std::net::SocketAddr;
+
+use cabin::prelude::*;
+use cabin::scope::event;
+use cabin::view::{Boundary, IteratorExt};
+use cabin::{Event, basic_document};
+use http::Request;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+const PAGE_SIZE: usize = 20;
+const TOTAL: usize = 200;
+
+async fn app() -> impl View {
+    basic_document(feed(1).await)
+}
+
+#[derive(Clone, Copy, Event, Serialize, Deserialize)]
+struct LoadPages(usize);
+
+#[cabin::boundary(LoadPages)]
+async fn feed(pages: usize) -> Boundary<usize> {
+    let pages = event::<LoadPages>().map(|e| e.0).unwrap_or(pages);
+    let shown = (pages * PAGE_SIZE).min(TOTAL);
+
+    view![
+        h::ul(
+            (1..=shown)
+                .keyed(|i| *i)
+                .map(|i| h::li(h::text!("Item {i}")).style("height:2rem"))
+        ),
+        // A sentinel after the last item loads the next page once it is scrolled into view.
+        (shown < TOTAL).then(|| h::div("loading…").on_visible(LoadPages(pages + 1))),
+    ]
+    .boundary(pages)
+}
+
+cabin::BOUNDARIES!();
+
+#[tokio::main]
+async fn main() {
+    let filter =
+        tracing_subscriber::filter::filter_fn(|metadata| metadata.target().starts_with("cabin"));
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::Layer::new().pretty())
+        .with(filter)
+        .init();
+
+    let server = axum::Router::new()
+        .route(
+            "/",
+            axum::routing::get(|| cabin::get_page(app))
+                .put(|req: Request<axum::body::Body>| cabin::put_page(req, app)),
+        )
+        .layer(cabin_service::redirects::layer())
+        .layer(cabin_service::boundaries::layer(&BOUNDARIES))
+        .layer(cabin_service::livereload::layer())
+        .layer(cabin_service::assets::layer());
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    println!("Listening on http://{addr}");
+    axum::serve(
+        TcpListener::bind(addr).await.unwrap(),
+        server.into_make_service(),
+    )
+    .await
+    .unwrap();
+}
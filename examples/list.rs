@@ -2,7 +2,7 @@ use std::net::SocketAddr;
 
 use cabin::prelude::*;
 use cabin::scope::take_event;
-use cabin::view::{Boundary, IteratorExt};
+use cabin::view::{Boundary, IteratorExt, Reorder};
 use cabin::{Event, basic_document};
 use http::Request;
 use serde::{Deserialize, Serialize};
@@ -24,6 +24,7 @@ enum ItemsEvent {
     AddBelow,
     Delete(usize),
     Increment(usize),
+    Reorder(Reorder<usize>),
 }
 
 #[cabin::boundary]
@@ -48,17 +49,29 @@ async fn list(mut items: Vec<Item>) -> Boundary<Vec<Item>> {
                     item.count += 1;
                 }
             }
+            ItemsEvent::Reorder(reorder) => {
+                reorder.apply(&mut items, |item| item.id);
+            }
         }
     }
 
     view![
         h::div(h::button("add above").on_click(ItemsEvent::AddAbove)),
-        h::ul(items.clone().into_iter().keyed(|item| item.id).map(|item| {
-            h::li![
-                h::button(h::text!("{}", item.count)).on_click(ItemsEvent::Increment(item.id)),
-                h::button("x").on_click(ItemsEvent::Delete(item.id)),
-            ]
-        })),
+        h::ul(
+            items
+                .clone()
+                .into_iter()
+                .keyed(|item| item.id)
+                .sortable(ItemsEvent::Reorder(Reorder::placeholder()))
+                .map(|item| {
+                    h::li![
+                        h::button(h::text!("{}", item.count))
+                            .on_click(ItemsEvent::Increment(item.id)),
+                        h::button("x").on_click(ItemsEvent::Delete(item.id)),
+                    ]
+                    .draggable()
+                })
+        ),
         h::div(h::button("add below").on_click(ItemsEvent::AddBelow)),
     ]
     .boundary(items.clone())
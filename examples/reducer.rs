@@ -0,0 +1,76 @@
+use std::net::SocketAddr;
+
+use cabin::prelude::*;
+use cabin::view::boundary::{Boundary, Reducer};
+use cabin::{Event, basic_document};
+use http::Request;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+
+async fn app() -> impl View {
+    basic_document(view![counter(Counter::default()), counter(Counter(10))])
+}
+
+#[derive(Default, Clone, Copy, Serialize, Deserialize)]
+struct Counter(i64);
+
+#[derive(Clone, Copy, Event, Serialize, Deserialize)]
+struct Increment;
+
+#[derive(Clone, Copy, Event, Serialize, Deserialize)]
+struct Reset;
+
+impl Reducer<Increment> for Counter {
+    fn update(self, _: Increment) -> Self {
+        Counter(self.0 + 1)
+    }
+}
+
+impl Reducer<Reset> for Counter {
+    fn update(self, _: Reset) -> Self {
+        Counter::default()
+    }
+}
+
+#[cabin::boundary(reduce(Increment, Reset), version = 1)]
+fn counter(state: Counter) -> Boundary<Counter> {
+    view![
+        h::button(h::text!("{}", state.0)).on_click(Increment),
+        h::button("reset").on_click(Reset),
+    ]
+    .boundary(state)
+}
+
+cabin::BOUNDARIES!();
+
+#[tokio::main]
+async fn main() {
+    let filter =
+        tracing_subscriber::filter::filter_fn(|metadata| metadata.target().starts_with("cabin"));
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::Layer::new().pretty())
+        .with(filter)
+        .init();
+
+    let server = axum::Router::new()
+        .route(
+            "/",
+            axum::routing::get(|| cabin::get_page(app))
+                .put(|req: Request<axum::body::Body>| cabin::put_page(req, app)),
+        )
+        .layer(cabin_service::redirects::layer())
+        .layer(cabin_service::boundaries::layer(&BOUNDARIES))
+        .layer(cabin_service::livereload::layer())
+        .layer(cabin_service::assets::layer());
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    println!("Listening on http://{addr}");
+    axum::serve(
+        TcpListener::bind(addr).await.unwrap(),
+        server.into_make_service(),
+    )
+    .await
+    .unwrap();
+}
@@ -1,22 +1,26 @@
 use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
+use std::{error, fmt};
 
 use bytes::Bytes;
-use http::{HeaderValue, Request, Response, StatusCode};
+use http::{HeaderName, HeaderValue, Request, Response, StatusCode};
 use http_body::Body;
+use http_error::HttpError;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use tracing::Instrument;
 
 use crate::View;
 use crate::error::InternalError;
 use crate::render::{Out, Renderer};
 use crate::scope::Scope;
-use crate::server::{err_to_response, parse_body};
+use crate::server::{err_to_response, html_response, parse_body, with_build_id};
+use crate::state_store::state_store;
 use crate::view::RenderFuture;
 use crate::view::boundary::BoundaryRef;
 
-type BoundaryHandler = dyn Send + Sync + Fn(&str, Renderer) -> RenderFuture;
+type BoundaryHandler = dyn Send + Sync + Fn(&str, u32, Renderer) -> RenderFuture;
 
 #[derive(Default)]
 pub struct BoundaryRegistry {
@@ -25,9 +29,21 @@ pub struct BoundaryRegistry {
 
 impl BoundaryRegistry {
     pub fn add(&mut self, boundaries: &'static [fn(&mut BoundaryRegistry)]) {
+        if let Err(err) = crate::id::check_unique() {
+            panic!(
+                "cabin id collision: {err}; rename one of them or disable the `short-ids` feature"
+            );
+        }
         for f in boundaries {
             (f)(self);
         }
+
+        #[cfg(debug_assertions)]
+        if cfg!(feature = "short-ids") {
+            for id in crate::id::IDS.iter() {
+                tracing::debug!(id = id.id, full_path = id.full_path, kind = ?id.kind, "cabin id");
+            }
+        }
     }
 
     pub fn register<Args>(&mut self, boundary: &'static BoundaryRef<Args>)
@@ -36,30 +52,86 @@ impl BoundaryRegistry {
     {
         self.handler.insert(
             boundary.id,
-            Arc::new(
-                |args_json: &str, r: Renderer| match serde_json::from_str(args_json) {
-                    Ok(args) => {
-                        crate::view::FutureExt::into_any_view(boundary.with(args)).render(r)
-                    }
-                    Err(err) => RenderFuture::Ready(Err(InternalError::Deserialize {
-                        what: "boundary state json",
-                        err: Box::new(err),
-                    }
-                    .into())),
-                },
-            ),
+            Arc::new(move |args_json: &str, version: u32, r: Renderer| {
+                if boundary.has_server_state() {
+                    let token = serde_json::from_str::<String>(args_json);
+                    return RenderFuture::Future(Box::pin(async move {
+                        let state_json = match token {
+                            Ok(token) => state_store().load(boundary.id, &token).await?,
+                            Err(_) => None,
+                        };
+                        let Some(state_json) = state_json else {
+                            return Err(crate::Error::from_http_err(StaleBoundaryState {
+                                id: boundary.id,
+                                reason: "boundary state expired".to_string(),
+                            }));
+                        };
+                        Self::render_boundary(boundary, &state_json, version, r).await
+                    }));
+                }
+
+                Self::render_boundary(boundary, args_json, version, r)
+            }),
         );
     }
 
+    fn render_boundary<Args>(
+        boundary: &'static BoundaryRef<Args>,
+        args_json: &str,
+        version: u32,
+        r: Renderer,
+    ) -> RenderFuture
+    where
+        Args: Clone + Serialize + DeserializeOwned + Send + Sync,
+    {
+        let args = if version == boundary.version() {
+            // State of the same version must deserialize, so failing to do so is a bug (e.g. an
+            // asymmetric serde implementation) rather than stale state
+            serde_json::from_str(args_json).map_err(|err| {
+                crate::Error::from(InternalError::Deserialize {
+                    what: "boundary state json",
+                    err: Box::new(err),
+                })
+            })
+        } else {
+            boundary.migrate(version, args_json).ok_or_else(|| {
+                crate::Error::from_http_err(StaleBoundaryState {
+                    id: boundary.id,
+                    reason: format!(
+                        "cannot migrate boundary state from version {version} to {}",
+                        boundary.version()
+                    ),
+                })
+            })
+        };
+        match args {
+            Ok(args) => crate::view::FutureExt::into_any_view(boundary.with(args)).render(r),
+            Err(err) => RenderFuture::Ready(Err(err)),
+        }
+    }
+
     pub fn handle<B>(&self, id: &str, req: Request<B>) -> impl Future<Output = Response<String>>
     where
         B: Body<Data = Bytes> + Send + 'static,
         B::Error: std::error::Error + Send + 'static,
     {
         let handler = self.handler.get(id).cloned();
+        let id = id.to_string();
+        let version = req
+            .headers()
+            .get(VERSION_HEADER_NAME)
+            .and_then(|v| v.to_str().ok()?.parse().ok())
+            .unwrap_or(0);
 
-        async move {
+        let span = tracing::info_span!(
+            "render_boundary",
+            boundary = full_path(&id),
+            event_id = tracing::field::Empty
+        );
+
+        let res = async move {
             let Some(handler) = handler else {
+                tracing::debug!(boundary = %id, "unknown boundary (e.g. removed by a deploy)");
                 return Response::builder()
                     .status(StatusCode::NOT_FOUND)
                     .body(String::new())
@@ -83,28 +155,194 @@ impl BoundaryRegistry {
                 Err(err) => return err_to_response(err.into()),
             };
 
+            span.record("event_id", &event.event_id);
             let mut scope = Scope::new(true, false).with_event(event.event_id, event.payload);
             if let Some(multipart) = event.multipart {
                 scope = scope.with_multipart(multipart);
             }
             let r = scope.create_renderer();
+            let after_response = scope.after_response();
+            #[cfg(feature = "server-timing")]
+            let timings = scope.timings();
             let result = scope
-                .run(async move { handler(state_json.get(), r).await })
+                .run(async move { handler(state_json.get(), version, r).await })
+                .instrument(span)
                 .await;
-            let Out { html, headers } = match result.and_then(|r| r.end()) {
+            #[allow(unused_mut)]
+            let Out { html, mut headers } = match result.and_then(|r| r.end()) {
                 Ok(result) => result,
                 Err(err) => return err_to_response(err),
             };
-            let mut res = Response::builder().header(
-                http::header::CONTENT_TYPE,
-                HeaderValue::from_static("text/html; charset=utf-8"),
-            );
-            for (key, value) in headers {
-                if let Some(key) = key {
-                    res = res.header(key, value);
-                }
-            }
-            res.body(html).unwrap()
-        }
+            #[cfg(feature = "server-timing")]
+            timings.insert_header(&mut headers, html.len());
+            after_response.spawn();
+            html_response(html, headers)
+        };
+        async move { with_build_id(res.await) }
+    }
+}
+
+/// The full path of the boundary `id` (which differs from the id with the `short-ids` feature).
+fn full_path(id: &str) -> &str {
+    crate::id::full_path(crate::id::IdKind::Boundary, id).unwrap_or(id)
+}
+
+const VERSION_HEADER_NAME: HeaderName = HeaderName::from_static("cabin-boundary-version");
+
+/// The boundary state sent by the client cannot be used anymore (e.g. after a deploy changed the
+/// boundary's arguments, or the server-side state expired). Makes the client refresh the whole
+/// page.
+#[derive(Debug)]
+struct StaleBoundaryState {
+    id: &'static str,
+    reason: String,
+}
+
+impl HttpError for StaleBoundaryState {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::CONFLICT
+    }
+
+    fn headers(&self) -> Option<Vec<(HeaderName, HeaderValue)>> {
+        Some(vec![(
+            HeaderName::from_static("cabin-stale-state"),
+            HeaderValue::from_static("1"),
+        )])
+    }
+}
+
+impl error::Error for StaleBoundaryState {}
+
+impl fmt::Display for StaleBoundaryState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "stale state of boundary `{}`: {}",
+            full_path(self.id),
+            self.reason
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::boundary::Boundary;
+
+    static BOUNDARY: BoundaryRef<u32> = BoundaryRef::new(
+        "counter",
+        &[],
+        &(|count: u32| Box::pin(async move { Boundary::new(count.to_string(), count) })),
+    )
+    .with_version(2)
+    .with_migration(|version, _| (version == 1).then_some(42));
+
+    static SERVER_STATE_BOUNDARY: BoundaryRef<u32> = BoundaryRef::new(
+        "server_state",
+        &[],
+        &(|count: u32| Box::pin(async move { Boundary::new(count.to_string(), count) })),
+    )
+    .with_server_state();
+
+    static INCREMENT_BOUNDARY: BoundaryRef<u32> = BoundaryRef::new(
+        "increment",
+        &[],
+        &(|count: u32| Box::pin(async move { Boundary::new(count.to_string(), count + 1) })),
+    )
+    .with_server_state();
+
+    async fn handle(version: u32, state: &str) -> Response<String> {
+        handle_boundary(&BOUNDARY, version, state).await
+    }
+
+    async fn handle_boundary(
+        boundary: &'static BoundaryRef<u32>,
+        version: u32,
+        state: &str,
+    ) -> Response<String> {
+        let mut registry = BoundaryRegistry::default();
+        registry.register(boundary);
+        let req = Request::builder()
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(VERSION_HEADER_NAME, version)
+            .body(format!(
+                r#"{{"eventId":"()","payload":null,"state":{state}}}"#
+            ))
+            .unwrap();
+        registry.handle(boundary.id, req).await
+    }
+
+    #[tokio::test]
+    async fn test_state_version() {
+        let res = handle(2, "1").await;
+        assert_eq!(res.status(), StatusCode::OK);
+        #[cfg(feature = "server-timing")]
+        assert!(res.headers().contains_key("server-timing"));
+
+        let res = handle(1, r#"{"old":true}"#).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.body().contains("42"), "{}", res.body());
+
+        let res = handle(0, "1").await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert!(res.headers().contains_key("cabin-stale-state"));
+
+        // state of the current version that fails to deserialize is a bug, not stale state
+        let res = handle(2, r#""not a number""#).await;
+        assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(!res.headers().contains_key("cabin-stale-state"));
+    }
+
+    #[tokio::test]
+    async fn test_build_id_header() {
+        let build_id = crate::server::build_id();
+        let res = handle(2, "1").await;
+        assert_eq!(res.headers()["cabin-build"], build_id);
+        // also for errors, e.g. for stale state
+        let res = handle(0, "1").await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert_eq!(res.headers()["cabin-build"], build_id);
+
+        // and for boundaries unknown to this build (e.g. removed by a deploy)
+        let req = Request::builder()
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(r#"{"eventId":"()","payload":null,"state":1}"#.to_string())
+            .unwrap();
+        let res = BoundaryRegistry::default().handle("removed", req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(res.headers()["cabin-build"], build_id);
+    }
+
+    #[tokio::test]
+    async fn test_server_state() {
+        let token = state_store()
+            .store(SERVER_STATE_BOUNDARY.id, "7".to_string())
+            .await
+            .unwrap();
+        let res = handle_boundary(&SERVER_STATE_BOUNDARY, 0, &format!(r#""{token}""#)).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.body().contains('7'), "{}", res.body());
+
+        // the replaced state is kept (e.g. for a request aborted by the client after the server
+        // already handled it), so replaying its token still works
+        let token = state_store()
+            .store(INCREMENT_BOUNDARY.id, "1".to_string())
+            .await
+            .unwrap();
+        let res = handle_boundary(&INCREMENT_BOUNDARY, 0, &format!(r#""{token}""#)).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!res.body().contains(&token), "{}", res.body());
+        let res = handle_boundary(&INCREMENT_BOUNDARY, 0, &format!(r#""{token}""#)).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let res = handle_boundary(&SERVER_STATE_BOUNDARY, 0, r#""expired""#).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert!(res.headers().contains_key("cabin-stale-state"));
+
+        // tokens of other boundaries are rejected
+        let token = state_store().store("other", "7".to_string()).await.unwrap();
+        let res = handle_boundary(&SERVER_STATE_BOUNDARY, 0, &format!(r#""{token}""#)).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        assert!(res.headers().contains_key("cabin-stale-state"));
     }
 }
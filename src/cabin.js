@@ -3,6 +3,10 @@
   const DECODER = new TextDecoder();
   const WASM = { href: null, wasm: null };
   const REFRESH_SYMBOL = Symbol("REFRESH_SYMBOL");
+  // The CSP nonce of the page (if any). Nonces in update responses are from other requests and thus
+  // don't match the page's Content-Security-Policy, which is why they are replaced with this one.
+  const NONCE = document.currentScript?.nonce ?? "";
+  const BUILD_ID = document.currentScript?.dataset.cabinBuild;
 
   /**
    * @return {Promise<WebAssembly.WebAssemblyInstantiatedSource?}
@@ -109,6 +113,9 @@
         }
       }
 
+      const version = target instanceof CabinBoundary ? target.getAttribute("version") : null;
+      const versionHeader = version ? { "cabin-boundary-version": version } : {};
+
       /** @type {RequestInit} */
       const req = (() => {
         if (payload instanceof FormData) {
@@ -138,6 +145,7 @@
             method: "PUT",
             headers: {
               "x-cabin": "boundary",
+              ...versionHeader,
             },
             body: formData,
           };
@@ -148,6 +156,7 @@
             headers: {
               "Content-Type": "application/json",
               "x-cabin": "boundary",
+              ...versionHeader,
             },
             body: `{"eventId":${JSON.stringify(eventId)},"payload":${JSON.stringify(payload)}${
               state ? `,"state":${state}` : ""
@@ -174,18 +183,38 @@
         return;
       }
 
-      const headerEventId = res.headers.get("cabin-event");
-      const headerPayload = res.headers.get("cabin-event-payload");
-      if (headerEventId && headerPayload) {
-        target.dispatchEvent(
-          new CustomEvent("cabinFire", {
-            detail: { eventId: headerEventId, payload: JSON.parse(headerPayload) },
-            bubbles: true,
-          }),
-        );
+      // The server runs another build than this page was loaded from (e.g. after a deploy)
+      const buildId = res.headers.get("cabin-build");
+      if (BUILD_ID && buildId && buildId !== BUILD_ID) {
+        console.warn("server was updated, reloading page");
+        reloadPreservingFormInput();
+        return;
+      }
+
+      // The boundary's state is incompatible with the server (e.g. after a deploy)
+      if (res.status === 409 && res.headers.has("cabin-stale-state")) {
+        console.warn("boundary state is stale, refreshing page");
+        document.dispatchEvent(new CustomEvent("cabinRefresh"));
+        return;
       }
 
+      // Fired events (JSON objects, joined by commas), dispatched in order
+      const firedEvents = res.headers.get("cabin-event");
+      if (firedEvents) {
+        for (const { eventId, payload } of JSON.parse(`[${firedEvents}]`)) {
+          target.dispatchEvent(
+            new CustomEvent("cabinFire", {
+              detail: { eventId, payload },
+              bubbles: true,
+            }),
+          );
+        }
+      }
+
+      const commands = res.headers.get("cabin-command");
+
       if (res.status === 204) {
+        runCommands(commands);
         return;
       }
 
@@ -205,6 +234,8 @@
       if (newTitle) {
         document.title = newTitle;
       }
+
+      runCommands(commands);
     } catch (err) {
       if (err instanceof DOMException && err.name === "AbortError") {
         // ignore
@@ -214,6 +245,139 @@
     }
   }
 
+  const FORM_INPUT_KEY = "cabin-form-input";
+
+  /**
+   * Form controls whose values are preserved across reloads, by a key that identifies them.
+   * @returns {Map<string, HTMLInputElement | HTMLTextAreaElement | HTMLSelectElement>}
+   */
+  function preservableControls() {
+    const controls = new Map();
+    const counts = new Map();
+    for (const el of document.querySelectorAll("input, textarea, select")) {
+      if (el instanceof HTMLInputElement && ["password", "file", "hidden"].includes(el.type)) {
+        continue;
+      }
+      let key = el.id ? `#${el.id}` : null;
+      if (!key && el.name) {
+        const name = `${el.form?.id ?? ""}:${el.name}`;
+        const count = counts.get(name) ?? 0;
+        counts.set(name, count + 1);
+        key = `${name}:${count}`;
+      }
+      if (key) {
+        controls.set(key, el);
+      }
+    }
+    return controls;
+  }
+
+  function reloadPreservingFormInput() {
+    const values = {};
+    for (const [key, el] of preservableControls()) {
+      if (el instanceof HTMLInputElement && (el.type === "checkbox" || el.type === "radio")) {
+        values[key] = el.checked;
+      } else if (el instanceof HTMLSelectElement && el.multiple) {
+        values[key] = Array.from(el.selectedOptions, (option) => option.value);
+      } else {
+        values[key] = el.value;
+      }
+    }
+    try {
+      sessionStorage.setItem(
+        FORM_INPUT_KEY,
+        JSON.stringify({ href: location.href, values }),
+      );
+    } catch (err) {
+      console.error(err);
+    }
+    location.reload();
+  }
+
+  function restoreFormInput() {
+    let saved;
+    try {
+      saved = JSON.parse(sessionStorage.getItem(FORM_INPUT_KEY));
+      sessionStorage.removeItem(FORM_INPUT_KEY);
+    } catch {
+      return;
+    }
+    if (!saved || saved.href !== location.href) {
+      return;
+    }
+
+    for (const [key, el] of preservableControls()) {
+      if (!(key in saved.values)) {
+        continue;
+      }
+      const value = saved.values[key];
+      if (el instanceof HTMLInputElement && (el.type === "checkbox" || el.type === "radio")) {
+        el.checked = !!value;
+      } else if (el instanceof HTMLSelectElement && el.multiple && Array.isArray(value)) {
+        for (const option of el.options) {
+          option.selected = value.includes(option.value);
+        }
+      } else {
+        el.value = value;
+      }
+    }
+  }
+
+  /**
+   * Execute the commands sent by the server via the `cabin-command` header.
+   * @param {string | null} header - JSON objects, joined by commas
+   */
+  function runCommands(header) {
+    if (!header) {
+      return;
+    }
+
+    for (const cmd of JSON.parse(`[${header}]`)) {
+      const el = cmd.selector ? document.querySelector(cmd.selector) : null;
+      if (cmd.selector && !el) {
+        console.warn(`${cmd.command}: no element found for selector ${cmd.selector}`);
+        continue;
+      }
+
+      switch (cmd.command) {
+        case "focus":
+          el.focus();
+          break;
+        case "scrollIntoView":
+          el.scrollIntoView({ behavior: cmd.smooth ? "smooth" : "auto", block: "nearest" });
+          break;
+        case "copyToClipboard":
+          navigator.clipboard?.writeText(cmd.text).catch((err) => console.error(err));
+          break;
+        case "showModal":
+          if (!el.open) {
+            el.showModal();
+          }
+          break;
+        case "closeDialog":
+          el.close();
+          break;
+        case "showPopover":
+          el.showPopover();
+          break;
+        case "hidePopover":
+          el.hidePopover();
+          break;
+        case "download": {
+          const a = document.createElement("a");
+          a.href = cmd.url;
+          a.download = cmd.filename ?? "";
+          document.body.appendChild(a);
+          a.click();
+          a.remove();
+          break;
+        }
+        default:
+          console.warn(`unknown command: ${cmd.command}`);
+      }
+    }
+  }
+
   /**
    * @param {string} html
    * @param {Node} target
@@ -223,6 +387,7 @@
     console.time("patch");
     const template = document.createElement("template");
     template.innerHTML = html;
+    applyNonce(template.content);
 
     if (template.content.firstElementChild instanceof HTMLStyleElement) {
       const style = template.content.removeChild(template.content.firstElementChild);
@@ -238,6 +403,12 @@
       patchChildren(document.head, headTemplate.content, {});
     }
 
+    // Out-of-band updates of elements outside of the target, applied after the target was patched
+    const outOfBand = Array.from(template.content.querySelectorAll("template[cabin-oob]"));
+    for (const oob of outOfBand) {
+      oob.remove();
+    }
+
     if (
       template.content.firstElementChild instanceof HTMLTemplateElement &&
       template.content.firstElementChild.id === "cabin-body"
@@ -250,21 +421,89 @@
       patchChildren(target, template.content, {}, disabledBefore);
     }
 
+    for (const oob of outOfBand) {
+      const id = oob.getAttribute("cabin-oob");
+      const el = document.getElementById(id);
+      if (el) {
+        patchChildren(el, oob.content, {}, disabledBefore);
+      } else {
+        console.warn(`out-of-band update: no element found with id ${id}`);
+      }
+    }
+
     console.timeEnd("patch");
   }
 
+  /**
+   * @param {DocumentFragment} fragment
+   */
+  function applyNonce(fragment) {
+    if (!NONCE) {
+      return;
+    }
+    for (const el of fragment.querySelectorAll("[nonce]")) {
+      el.setAttribute("nonce", NONCE);
+      el.nonce = NONCE;
+    }
+    for (const template of fragment.querySelectorAll("template")) {
+      applyNonce(template.content);
+    }
+  }
+
+  /**
+   * Ask the user for confirmation, if requested by the element that defines the event.
+   * @param {Element} node
+   * @returns {Promise<boolean>} whether the event should be fired
+   */
+  async function confirmEvent(node) {
+    const message = node.getAttribute("cabin-confirm");
+    if (message !== null) {
+      return window.confirm(message);
+    }
+
+    const dialogId = node.getAttribute("cabin-confirm-dialog");
+    if (dialogId !== null) {
+      const dialog = document.getElementById(dialogId);
+      if (!(dialog instanceof HTMLDialogElement)) {
+        console.error(`confirm dialog #${dialogId} not found`);
+        return false;
+      }
+      dialog.returnValue = "";
+      dialog.showModal();
+      await new Promise((resolve) => dialog.addEventListener("close", resolve, { once: true }));
+      return dialog.returnValue === "confirm";
+    }
+
+    return true;
+  }
+
+  /**
+   * Per element event state (debounce, throttle and once).
+   * @type {WeakMap<Element, Record<string, any>>}
+   */
+  const elementState = new WeakMap();
+
+  /**
+   * Events already handled by the listener of a descendant.
+   * @type {WeakSet<Event>}
+   */
+  const handledEvents = new WeakSet();
+
   /**
    * @param {HTMLElement} el
    * @param {string} eventName
    * @param {Object} opts
    * @param {Set<string>?} opts.events - list of events the boundary should handle
-   * @param {bool?} opts.preventDefault - whether the default of the event should be prevented
+   * @param {bool | ((e: Event, node: Element) => boolean)?} opts.preventDefault - whether the
+   * default of the event should be prevented
    * @param {bool?} opts.disable - whether the element should be disabled while the event is handled
    * @param {bool?} opts.disableForm - whether to disable the target's form (but only up to the next
    * boundary)
-   * @param {(e: Event) => Record<string, any>} opts.eventPayload - custom event payload
    * @param {number} opts.debounce - if set, the event execution is debounced by the given
    * milliseconds
+   * @param {(e: Event, node: Element) => boolean} opts.filter - if set, only ascendants for which
+   * the filter returns true handle the event
+   * @param {string?} opts.listenTo - the DOM event to listen to, if it differs from `eventName`
    */
   function setUpEventListener(el, eventName, opts) {
     const attrName = `cabin-${eventName}`;
@@ -274,6 +513,10 @@
      * @param {Event} e
      */
     async function handleEvent(e) {
+      if (handledEvents.has(e)) {
+        return;
+      }
+
       /** @type {Element} */
       let node = e.target;
       /** @type {string | undefined} */
@@ -283,7 +526,7 @@
       do {
         eventId =
           e.detail?.eventId ?? e.submitter?.getAttribute(attrName) ?? node.getAttribute(attrName);
-        if (!eventId) {
+        if (!eventId || (opts.filter && !opts.filter(e, node))) {
           continue;
         } else {
           break;
@@ -351,14 +594,91 @@
 
       // Note: `e.preventDefault()` doesn't seem to take full effect when called after
       // `abortController.abort()`, so ensure it stays before that
-      e.stopPropagation();
       const isSubmitEvent = eventName === "submit";
-      if ((opts.preventDefault && !(node instanceof HTMLInputElement)) || isSubmitEvent) {
+      const preventDefault = node.getAttribute("cabin-prevent-default");
+      // A per-event decision (e.g. for specific keys) also applies to inputs, a general default
+      // doesn't (e.g. clicking a checkbox must still toggle it)
+      const preventDefaultByDefault =
+        typeof opts.preventDefault === "function"
+          ? opts.preventDefault(e, node)
+          : opts.preventDefault && !(node instanceof HTMLInputElement);
+      // Submit events are always prevented, as the form would otherwise be submitted twice (by
+      // the browser and by cabin)
+      if (
+        isSubmitEvent ||
+        (preventDefault === null ? preventDefaultByDefault : preventDefault === "true")
+      ) {
         e.preventDefault();
       }
+      // Listeners of ascendants must not handle the event again. Keydowns that don't prevent the
+      // default (e.g. of `Key::Any` filters) still propagate to reach document-wide shortcuts.
+      handledEvents.add(e);
+      if (!(eventName === "keydown" && !e.defaultPrevented)) {
+        e.stopPropagation();
+      }
+
+      /** @type {Record<string, any>} */
+      let state = elementState.get(node);
+      if (!state) {
+        elementState.set(node, (state = {}));
+      }
+
+      const once = node.hasAttribute("cabin-once");
+      if (once && state[`${eventName}:fired`]) {
+        return;
+      }
+
+      const throttle = Number(node.getAttribute("cabin-throttle") ?? 0);
+      if (throttle > 0) {
+        const now = performance.now();
+        if (now - (state[`${eventName}:lastFired`] ?? -Infinity) < throttle) {
+          return;
+        }
+        state[`${eventName}:lastFired`] = now;
+      }
 
-      // Only one concurrent event execution per boundary
-      if (this.abortController) {
+      // Debounce per element. This must stay after e.preventDefault() and e.stopPropagation() to
+      // ensure they are not postponed by the promise.
+      const debounce = Number(node.getAttribute("cabin-debounce") ?? opts.debounce ?? 0);
+      if (debounce > 0) {
+        const token = (state[`${eventName}:debounce`] = {});
+        await new Promise((resolve) => setTimeout(resolve, debounce));
+        if (state[`${eventName}:debounce`] !== token) {
+          // superseded by a later event
+          return;
+        }
+      }
+
+      // Ask for confirmation once the event is certain to fire (i.e. after debounce and throttle),
+      // but before anything else (like aborting in-flight requests or disabling elements) happens
+      if (!(await confirmEvent(node))) {
+        return;
+      }
+
+      // Only mark as fired once confirmed, and check again as another event could have fired while
+      // waiting for the confirmation
+      if (once) {
+        if (state[`${eventName}:fired`]) {
+          return;
+        }
+        state[`${eventName}:fired`] = true;
+      }
+
+      // Only one concurrent event execution per boundary, what happens to additional events is
+      // decided by the boundary's concurrency policy
+      const concurrency = (el !== document && el.getAttribute("concurrency")) || "abort";
+      const inFlight = this.abortController && !this.abortController.signal.aborted;
+      /** @type {(() => void) | undefined} */
+      let releaseQueue = undefined;
+      if (concurrency === "drop") {
+        if (inFlight) {
+          return;
+        }
+      } else if (concurrency === "queue") {
+        const previous = this.queue;
+        this.queue = new Promise((resolve) => (releaseQueue = resolve));
+        await previous;
+      } else if (this.abortController) {
         this.abortController.abort();
       }
 
@@ -390,31 +710,18 @@
         }
       };
 
-      // Do not make the parent function async to ensure e.preventDefault() and e.stopPropagation()
-      // are not postponed by the promise.
-      if (opts.debounce) {
-        await new Promise((resolve) => setTimeout(resolve, opts.debounce));
-        if (abortController.signal.aborted) {
-          return;
-        }
-      }
-
       try {
         let payload;
         if (isSubmitEvent && !node.hasAttribute(`${attrName}-payload`)) {
           payload = new FormData(node);
-        } else if (opts?.eventPayload) {
-          payload = JSON.parse(
-            Object.entries(opts.eventPayload(e)).reduce(
-              (result, [placeholder, value]) => result.replace(placeholder, value),
-              node.getAttribute(`${attrName}-payload`),
-            ),
-          );
+        } else if (e.detail && typeof e.detail === "object" && "payload" in e.detail) {
+          payload = e.detail.payload;
         } else {
-          payload =
-            e.detail && typeof e.detail === "object" && "payload" in e.detail
-              ? e.detail.payload
-              : JSON.parse(node.getAttribute(`${attrName}-payload`));
+          payload = resolvePlaceholders(
+            JSON.parse(node.getAttribute(`${attrName}-payload`)),
+            e,
+            node,
+          );
         }
 
         if (isSubmitEvent) {
@@ -469,10 +776,11 @@
         throw err;
       } finally {
         abortController.abort(); // restore disabled states
+        releaseQueue?.();
       }
     }
 
-    el.addEventListener(eventName, function (e) {
+    el.addEventListener(opts.listenTo ?? eventName, function (e) {
       handleEvent.call(this, e).catch((err) => {
         console.error(err);
       });
@@ -717,6 +1025,12 @@
    * @param {string} attr
    */
   function ignoreAttribute(el, attr) {
+    // Browsers hide the nonce attribute once the element got inserted, so never patch it (the
+    // element keeps its nonce internally).
+    if (attr === "nonce") {
+      return true;
+    }
+
     switch (el.nodeName) {
       case "DIALOG":
         return attr === "open";
@@ -733,6 +1047,159 @@
     return node.nodeType === Node.ELEMENT_NODE && node.nodeName === "CABIN-KEYED";
   }
 
+  /**
+   * @param {KeyboardEvent} e
+   * @param {{ key: string | null, ctrl?: bool, shift?: bool, alt?: bool, meta?: bool }} filter
+   */
+  function matchesKey(e, filter) {
+    if (e.repeat || e.isComposing) {
+      return false;
+    }
+    if (filter.key === null) {
+      // Any key: only require the requested modifiers
+      return (
+        (!filter.ctrl || e.ctrlKey) &&
+        (!filter.shift || e.shiftKey) &&
+        (!filter.alt || e.altKey) &&
+        (!filter.meta || e.metaKey)
+      );
+    }
+    if (e.key.toLowerCase() !== filter.key.toLowerCase()) {
+      return false;
+    }
+    // Shift is often required to type a character in the first place, so ignore it for printable
+    // characters unless explicitly requested
+    const ignoreShift = filter.key.length === 1 && !filter.shift;
+    return (
+      e.ctrlKey === !!filter.ctrl &&
+      (ignoreShift || e.shiftKey === !!filter.shift) &&
+      e.altKey === !!filter.alt &&
+      e.metaKey === !!filter.meta
+    );
+  }
+
+  /**
+   * @param {string} attrName
+   * @returns {(e: KeyboardEvent, node: Element) => boolean}
+   */
+  function keyFilter(attrName) {
+    return function (e, node) {
+      const filter = node.getAttribute(`${attrName}-key`);
+      return filter === null || matchesKey(e, JSON.parse(filter));
+    };
+  }
+
+  /**
+   * Names of all known placeholders (without their `_##` prefix and `:` argument).
+   */
+  const PLACEHOLDERS = new Set([
+    "InputValue",
+    "InputChecked",
+    "InputKey",
+    "SelectedValues",
+    "SelectionRange",
+    "PointerPosition",
+    "ScrollPosition",
+    "Files",
+    "DraggedKey",
+    "DropKey",
+    "data",
+    "form",
+  ]);
+
+  /**
+   * @param {string} value
+   * @returns {boolean}
+   */
+  function isPlaceholder(value) {
+    if (!value.startsWith("_##")) {
+      return false;
+    }
+    const sep = value.indexOf(":");
+    return PLACEHOLDERS.has(sep === -1 ? value.substring(3) : value.substring(3, sep));
+  }
+
+  /**
+   * Replace all placeholders (known placeholder names prefixed with `_##`) in the event payload
+   * with the actual values they refer to. Other strings (even if they start with `_##`) are kept
+   * as is.
+   * @param {any} value
+   * @param {Event} e
+   * @param {Element} node - the element that defines the event
+   */
+  function resolvePlaceholders(value, e, node) {
+    if (typeof value === "string") {
+      return isPlaceholder(value) ? resolvePlaceholder(value.substring(3), e, node) : value;
+    }
+    if (Array.isArray(value)) {
+      return value.map((v) => resolvePlaceholders(v, e, node));
+    }
+    if (value !== null && typeof value === "object") {
+      for (const key in value) {
+        value[key] = resolvePlaceholders(value[key], e, node);
+      }
+    }
+    return value;
+  }
+
+  /**
+   * @param {string} placeholder
+   * @param {Event} e
+   * @param {Element} node
+   */
+  function resolvePlaceholder(placeholder, e, node) {
+    const target = e.target;
+    const sep = placeholder.indexOf(":");
+    const kind = sep === -1 ? placeholder : placeholder.substring(0, sep);
+    const arg = sep === -1 ? undefined : placeholder.substring(sep + 1);
+    switch (kind) {
+      case "InputValue":
+        return target.value ?? "";
+      case "InputChecked":
+        return !!target.checked;
+      case "InputKey":
+        return e.key ?? "";
+      case "SelectedValues":
+        return Array.from(target.selectedOptions ?? [], (option) => option.value);
+      case "SelectionRange":
+        return { start: target.selectionStart ?? 0, end: target.selectionEnd ?? 0 };
+      case "PointerPosition":
+        return {
+          clientX: e.clientX ?? 0,
+          clientY: e.clientY ?? 0,
+          offsetX: e.offsetX ?? 0,
+          offsetY: e.offsetY ?? 0,
+        };
+      case "ScrollPosition":
+        return { x: node.scrollLeft ?? 0, y: node.scrollTop ?? 0 };
+      case "Files":
+        return Array.from(target.files ?? [], (file) => ({
+          name: file.name,
+          size: file.size,
+          type: file.type,
+          lastModified: file.lastModified,
+        }));
+      case "DraggedKey":
+        return dragged?.hasAttribute("cabin-key")
+          ? JSON.parse(dragged.getAttribute("cabin-key"))
+          : null;
+      case "DropKey": {
+        const keyed = target.closest?.("cabin-keyed[cabin-key]");
+        return keyed ? JSON.parse(keyed.getAttribute("cabin-key")) : null;
+      }
+      case "data":
+        return node.getAttribute(`data-${arg}`);
+      case "form": {
+        const form = node.form ?? node.closest("form");
+        const control = form?.elements.namedItem(arg);
+        return control?.value ?? null;
+      }
+      default:
+        console.warn(`unknown payload placeholder: ${placeholder}`);
+        return `_##${placeholder}`;
+    }
+  }
+
   /**
    * @param {HTMLElement} el
    */
@@ -767,19 +1234,47 @@
     });
     setUpEventListener(el, "change", {
       events,
-      eventPayload: (e) => ({
-        "_##InputValue": e.target.value,
-        '"_##InputChecked"': e.target.checked,
-      }),
       disableForm: true,
     });
     setUpEventListener(el, "input", {
       events,
       debounce: 500,
-      eventPayload: (e) => ({
-        "_##InputValue": e.target.value,
-        '"_##InputChecked"': e.target.checked,
-      }),
+    });
+    // focus/blur don't bubble, so listen to their bubbling counterparts instead
+    setUpEventListener(el, "focus", {
+      events,
+      listenTo: "focusin",
+    });
+    setUpEventListener(el, "blur", {
+      events,
+      listenTo: "focusout",
+    });
+    setUpEventListener(el, "visible", {
+      events,
+    });
+    setUpEventListener(el, "hidden", {
+      events,
+    });
+    setUpEventListener(el, "keydown", {
+      events,
+      // Only prevent the default for specific keys, so that listening to any key (e.g. on a
+      // wrapper) doesn't block typing
+      preventDefault: (e, node) => {
+        const filter = node.getAttribute("cabin-keydown-key");
+        return filter !== null && JSON.parse(filter).key !== null;
+      },
+      filter: keyFilter("cabin-keydown"),
+    });
+    setUpEventListener(el, "keyup", {
+      events,
+      filter: keyFilter("cabin-keyup"),
+    });
+    setUpEventListener(el, "dragstart", {
+      events,
+    });
+    setUpEventListener(el, "drop", {
+      events,
+      preventDefault: true,
     });
     setUpEventListener(el, "submit", {
       events,
@@ -802,6 +1297,140 @@
   customElements.define("cabin-boundary", CabinBoundary);
 
   setupEventListeners(document);
+  restoreFormInput();
+
+  // Keep track of the dragged keyed item (not cleared on `dragend`, as it is still needed when
+  // resolving the payload of the `drop` event, which is potentially delayed).
+  /** @type {Element | null} */
+  let dragged = null;
+  document.addEventListener(
+    "dragstart",
+    function (e) {
+      dragged = e.target instanceof Element ? e.target.closest("cabin-keyed") : null;
+      if (dragged && e.dataTransfer) {
+        e.dataTransfer.effectAllowed = "move";
+        // required for Firefox to start dragging
+        e.dataTransfer.setData("text/plain", "");
+      }
+    },
+    { capture: true },
+  );
+  // Allow dropping onto elements that define a drop event
+  document.addEventListener("dragover", function (e) {
+    if (e.target instanceof Element && e.target.closest("[cabin-drop]")) {
+      e.preventDefault();
+      if (e.dataTransfer) {
+        e.dataTransfer.dropEffect = "move";
+      }
+    }
+  });
+
+  // Fire `visible`/`hidden` events for elements with `cabin-visible`/`cabin-hidden` attributes
+  // once they enter/leave the viewport.
+  /** @type {WeakMap<Element, bool>} */
+  const isVisible = new WeakMap();
+  const intersectionObserver = new IntersectionObserver((entries) => {
+    for (const entry of entries) {
+      const wasVisible = isVisible.get(entry.target);
+      isVisible.set(entry.target, entry.isIntersecting);
+      if (entry.isIntersecting && !wasVisible) {
+        entry.target.dispatchEvent(new CustomEvent("visible", { bubbles: true }));
+      } else if (!entry.isIntersecting && wasVisible) {
+        // only fire after the element was visible before
+        entry.target.dispatchEvent(new CustomEvent("hidden", { bubbles: true }));
+      }
+    }
+  });
+  const visibilitySelector = "[cabin-visible],[cabin-hidden]";
+  /**
+   * @param {Element} el
+   */
+  function observeVisibility(el) {
+    // (re-)observing an element also re-evaluates its current visibility, which allows e.g. an
+    // infinite scroll sentinel that is still visible after an update to fire again
+    intersectionObserver.unobserve(el);
+    isVisible.delete(el);
+    intersectionObserver.observe(el);
+  }
+  for (const el of document.querySelectorAll(visibilitySelector)) {
+    observeVisibility(el);
+  }
+  new MutationObserver((mutations) => {
+    for (const mutation of mutations) {
+      if (mutation.type === "attributes") {
+        if (mutation.target.matches(visibilitySelector)) {
+          observeVisibility(mutation.target);
+        } else {
+          intersectionObserver.unobserve(mutation.target);
+        }
+        continue;
+      }
+      for (const node of mutation.removedNodes) {
+        if (node instanceof Element) {
+          for (const el of [node, ...node.querySelectorAll(visibilitySelector)]) {
+            intersectionObserver.unobserve(el);
+          }
+        }
+      }
+      for (const node of mutation.addedNodes) {
+        if (node instanceof Element) {
+          if (node.matches(visibilitySelector)) {
+            observeVisibility(node);
+          }
+          for (const el of node.querySelectorAll(visibilitySelector)) {
+            observeVisibility(el);
+          }
+        }
+      }
+    }
+  }).observe(document.documentElement, {
+    subtree: true,
+    childList: true,
+    attributes: true,
+    attributeFilter: [
+      "cabin-visible",
+      "cabin-visible-payload",
+      "cabin-hidden",
+      "cabin-hidden-payload",
+    ],
+  });
+
+  // Document-wide shortcuts, registered via `<cabin-shortcut>` elements. Skipped if an element
+  // already handled the keydown for a specific key (i.e. prevented its default).
+  document.addEventListener("keydown", function (e) {
+    if (e.defaultPrevented) {
+      return;
+    }
+
+    const target = e.target;
+    const editable =
+      target instanceof HTMLElement &&
+      (target.isContentEditable || ["INPUT", "TEXTAREA", "SELECT"].includes(target.nodeName));
+
+    for (const el of document.querySelectorAll("cabin-shortcut")) {
+      const filter = JSON.parse(el.getAttribute("cabin-keydown-key"));
+      if (editable && !filter.ctrl && !filter.alt && !filter.meta) {
+        continue;
+      }
+      if (!matchesKey(e, filter)) {
+        continue;
+      }
+
+      e.preventDefault();
+      const payload = resolvePlaceholders(
+        JSON.parse(el.getAttribute("cabin-keydown-payload")),
+        e,
+        el,
+      );
+      el.dispatchEvent(
+        new CustomEvent("cabinFire", {
+          detail: { eventId: el.getAttribute("cabin-keydown"), payload },
+          bubbles: true,
+        }),
+      );
+      return;
+    }
+  });
 
   document.addEventListener("cabinRefresh", async function () {
     // Force update all boundary content
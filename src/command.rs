@@ -0,0 +1,215 @@
+//! Commands the server can ask the client to execute after an update (e.g. focus an input). Each
+//! command is a [View] that adds itself to the response, e.g.:
+//!
+//! ```rust,ignore
+//! view![
+//!     h::input().id("search"),
+//!     cabin::command::focus("#search"),
+//! ]
+//! ```
+//!
+//! Elements are referenced via CSS selectors. Commands are executed in order after the response
+//! was applied to the page.
+
+use std::borrow::Cow;
+
+use http::{HeaderName, HeaderValue};
+use serde::Serialize;
+
+use crate::View;
+use crate::error::InternalError;
+use crate::render::{Renderer, json_header_value};
+use crate::view::RenderFuture;
+
+pub(crate) const HEADER_NAME: HeaderName = HeaderName::from_static("cabin-command");
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(tag = "command", rename_all = "camelCase")]
+pub enum Command {
+    /// Focus the element.
+    Focus { selector: Cow<'static, str> },
+    /// Scroll the element into view.
+    ScrollIntoView {
+        selector: Cow<'static, str>,
+        smooth: bool,
+    },
+    /// Copy the text to the user's clipboard.
+    CopyToClipboard { text: Cow<'static, str> },
+    /// Open the `<dialog>` element as modal.
+    ShowModal { selector: Cow<'static, str> },
+    /// Close the `<dialog>` element.
+    CloseDialog { selector: Cow<'static, str> },
+    /// Show the popover element.
+    ShowPopover { selector: Cow<'static, str> },
+    /// Hide the popover element.
+    HidePopover { selector: Cow<'static, str> },
+    /// Download the file at the url. The `filename` is only a suggestion to the browser.
+    Download {
+        url: Cow<'static, str>,
+        filename: Option<Cow<'static, str>>,
+    },
+}
+
+/// Focus the element matching the `selector`.
+pub fn focus(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::Focus {
+        selector: selector.into(),
+    }
+}
+
+/// Scroll the element matching the `selector` into view.
+pub fn scroll_into_view(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::ScrollIntoView {
+        selector: selector.into(),
+        smooth: false,
+    }
+}
+
+/// Smoothly scroll the element matching the `selector` into view.
+pub fn scroll_into_view_smooth(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::ScrollIntoView {
+        selector: selector.into(),
+        smooth: true,
+    }
+}
+
+/// Copy the `text` to the user's clipboard.
+pub fn copy_to_clipboard(text: impl Into<Cow<'static, str>>) -> Command {
+    Command::CopyToClipboard { text: text.into() }
+}
+
+/// Open the `<dialog>` matching the `selector` as modal.
+pub fn show_modal(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::ShowModal {
+        selector: selector.into(),
+    }
+}
+
+/// Close the `<dialog>` matching the `selector`.
+pub fn close_dialog(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::CloseDialog {
+        selector: selector.into(),
+    }
+}
+
+/// Show the popover matching the `selector`.
+pub fn show_popover(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::ShowPopover {
+        selector: selector.into(),
+    }
+}
+
+/// Hide the popover matching the `selector`.
+pub fn hide_popover(selector: impl Into<Cow<'static, str>>) -> Command {
+    Command::HidePopover {
+        selector: selector.into(),
+    }
+}
+
+/// Download the file at the `url`.
+pub fn download(url: impl Into<Cow<'static, str>>) -> Command {
+    Command::Download {
+        url: url.into(),
+        filename: None,
+    }
+}
+
+impl Command {
+    /// Suggest a filename for a [Command::Download].
+    pub fn filename(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        if let Command::Download { filename, .. } = &mut self {
+            *filename = Some(name.into());
+        }
+        self
+    }
+
+    fn to_header_value(&self) -> Result<HeaderValue, crate::Error> {
+        let json = serde_json::to_string(self).map_err(|err| InternalError::Serialize {
+            what: "command".into(),
+            err,
+        })?;
+        json_header_value(&json)
+    }
+}
+
+impl View for Command {
+    fn render(self, mut r: Renderer) -> RenderFuture {
+        match self.to_header_value() {
+            Ok(value) => {
+                // Multiple commands are sent as multiple header values (joined by commas by the
+                // client), which is why they are appended.
+                r.headers_mut().append(HEADER_NAME, value);
+                RenderFuture::Ready(Ok(r))
+            }
+            Err(err) => RenderFuture::Ready(Err(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_commands() {
+        let out = crate::view![focus("#search"), copy_to_clipboard("grüße")]
+            .render(Renderer::new(false, true))
+            .await
+            .unwrap()
+            .end()
+            .unwrap();
+        assert_eq!(
+            out.headers
+                .get_all(HEADER_NAME)
+                .iter()
+                .map(|v| v.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                r##"{"command":"focus","selector":"#search"}"##,
+                r#"{"command":"copyToClipboard","text":"gr\u00fc\u00dfe"}"#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commands_response() {
+        let res =
+            crate::server::get_page(|| std::future::ready(crate::view![focus("#a"), focus("#b")]))
+                .await;
+        assert_eq!(
+            res.headers()
+                .get_all(HEADER_NAME)
+                .iter()
+                .map(|v| v.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                r##"{"command":"focus","selector":"#a"}"##,
+                r##"{"command":"focus","selector":"#b"}"##,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_commands_order() {
+        let mut r = focus("#a")
+            .render(Renderer::new(false, true))
+            .await
+            .unwrap();
+        // a child renderer whose header map grew larger before (e.g. from the renderer pool)
+        let mut child = Renderer::new(false, true);
+        child.headers_mut().reserve(64);
+        r.append(focus("#b").render(child).await.unwrap());
+        let out = r.end().unwrap();
+        assert_eq!(
+            out.headers
+                .get_all(HEADER_NAME)
+                .iter()
+                .map(|v| v.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                r##"{"command":"focus","selector":"#a"}"##,
+                r##"{"command":"focus","selector":"#b"}"##,
+            ]
+        );
+    }
+}
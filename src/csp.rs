@@ -0,0 +1,97 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::future::Future;
+
+use crate::html::attributes::Attributes;
+use crate::render::ElementRenderer;
+
+tokio::task_local! {
+    static NONCE: Nonce;
+}
+
+/// Cryptographic nonce ("number used once") used by Content Security Policy to allow the scripts
+/// and styles emitted by cabin.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nonce(String);
+
+impl Nonce {
+    /// Generate a new random nonce (128 bits, hex encoded).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn generate() -> Result<Self, crate::Error> {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes).map_err(crate::Error::from_err)?;
+        let mut nonce = String::with_capacity(bytes.len() * 2);
+        for b in bytes {
+            write!(&mut nonce, "{b:02x}").unwrap();
+        }
+        Ok(Self(nonce))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Nonce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<Nonce> for Cow<'static, str> {
+    fn from(nonce: Nonce) -> Self {
+        Cow::Owned(nonce.0)
+    }
+}
+
+/// Run the given future with the given `nonce`. Pages and boundaries rendered as part of the
+/// future pick up the nonce and apply it to all scripts and styles emitted by cabin.
+pub async fn with_nonce<F: Future>(nonce: Nonce, f: F) -> F::Output {
+    NONCE.scope(nonce, f).await
+}
+
+pub(crate) fn current() -> Option<Nonce> {
+    NONCE.try_with(|nonce| nonce.clone()).ok()
+}
+
+/// Renders the `nonce` attribute of the current scope, if there is any.
+pub(crate) struct ScopeNonce;
+
+impl Attributes for ScopeNonce {
+    fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
+        if let Some(nonce) = crate::scope::nonce() {
+            r.unhashed_attribute("nonce", nonce)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::View;
+    use crate::scope::Scope;
+
+    #[tokio::test]
+    async fn test_nonce_attribute() {
+        let nonce = Nonce::generate().unwrap();
+        let html = with_nonce(nonce.clone(), async {
+            let scope = Scope::new(false, true);
+            let r = scope.create_renderer();
+            scope
+                .run(crate::document(crate::h::p(crate::h::text!("content"))).render(r))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html
+        })
+        .await;
+
+        let attr = format!(r#" nonce="{nonce}""#);
+        let scripts = html.matches("<script ").count();
+        assert!(scripts > 0);
+        assert_eq!(html.matches(&format!("<script{attr}")).count(), scripts);
+        assert!(html.contains(&format!("<style{attr}")));
+    }
+}
@@ -0,0 +1,261 @@
+use std::borrow::Cow;
+
+use crate::html::Html;
+use crate::html::attributes::Attributes;
+use crate::html::elements::common::Class;
+use crate::html::elements::global::Dir;
+use crate::html::elements::link::Rel;
+use crate::html::elements::meta::Charset;
+use crate::prelude::*;
+use crate::render::{ElementRenderer, Renderer};
+use crate::style::collector::{StyleCollector, StyleDelegate};
+use crate::style::{Style, StyleDefinition, StyleModifier, SubStyle};
+use crate::view::{AnyView, BoxedView, FutureExt, RenderFuture, UpdateView};
+
+/// Create a full HTML document (`<!DOCTYPE html><html><head>…</head><body>…</body></html>`)
+/// around the given `content`. Use the builder methods of [Document] to customize the document.
+pub fn document(content: impl View) -> Document {
+    Document::new(content)
+}
+
+/// A full HTML document. Collects the styles of its content into the document's `head` and
+/// includes the scripts required by cabin.
+pub struct Document {
+    lang: Option<Cow<'static, str>>,
+    dir: Option<Dir>,
+    charset: Option<Charset>,
+    viewport: Option<Cow<'static, str>>,
+    favicon: Option<Cow<'static, str>>,
+    head: Vec<BoxedView>,
+    body_class: Class,
+    body_style: Option<StyleCollector>,
+    livereload: bool,
+    content: AnyView,
+}
+
+impl Document {
+    pub fn new(content: impl View) -> Self {
+        Self {
+            lang: None,
+            dir: None,
+            charset: Some(Charset::Utf8),
+            viewport: Some(Cow::Borrowed("width=device-width, initial-scale=1")),
+            favicon: None,
+            head: Vec::new(),
+            body_class: Class::default(),
+            body_style: None,
+            livereload: cfg!(feature = "livereload"),
+            content: content.into_any_view(),
+        }
+    }
+
+    /// Primary language of the document (`<html lang="…">`).
+    pub fn lang(mut self, lang: impl Into<Cow<'static, str>>) -> Self {
+        self.lang = Some(lang.into());
+        self
+    }
+
+    /// The document's text directionality (`<html dir="…">`).
+    pub fn dir(mut self, dir: Dir) -> Self {
+        self.dir = Some(dir);
+        self
+    }
+
+    /// Character encoding declaration. Defaults to [Charset::Utf8], set to `None` to omit the
+    /// `<meta charset>` element.
+    pub fn charset(mut self, charset: impl Into<Option<Charset>>) -> Self {
+        self.charset = charset.into();
+        self
+    }
+
+    /// Content of the `<meta name="viewport">` element. Defaults to
+    /// `width=device-width, initial-scale=1`, set to `None` to omit the element.
+    pub fn viewport(mut self, viewport: impl Into<Option<Cow<'static, str>>>) -> Self {
+        self.viewport = viewport.into();
+        self
+    }
+
+    /// Address of an icon that represents the document (`<link rel="icon">`).
+    pub fn favicon(mut self, href: impl Into<Cow<'static, str>>) -> Self {
+        self.favicon = Some(href.into());
+        self
+    }
+
+    /// Add an additional view (e.g. [h::title], [h::link], [h::meta]) to the document's `head`.
+    pub fn head(mut self, view: impl View) -> Self {
+        self.head.push(view.boxed());
+        self
+    }
+
+    /// The various classes the document's `body` belongs to.
+    pub fn body_class(mut self, class: impl Into<Cow<'static, str>>) -> Self {
+        self.body_class = std::mem::take(&mut self.body_class).append(Class(class.into()));
+        self
+    }
+
+    /// Whether to include the livereload script. Defaults to `true`. Only available with the
+    /// `livereload` feature, without it the script is never included.
+    #[cfg(feature = "livereload")]
+    pub fn livereload(mut self, livereload: bool) -> Self {
+        self.livereload = livereload;
+        self
+    }
+}
+
+impl View for Document {
+    fn render(self, r: Renderer) -> RenderFuture {
+        let Document {
+            lang,
+            dir,
+            charset,
+            viewport,
+            favicon,
+            head,
+            body_class,
+            body_style,
+            livereload,
+            content,
+        } = self;
+
+        let mut body = Html::<marker::Body, _>::new("body", body_class, content);
+        if let Some(style) = body_style {
+            body = body.with_style(style);
+        }
+        let body = UpdateView::template_on_update("cabin-body", body);
+
+        #[allow(clippy::async_yields_async)]
+        async move {
+            let (body, styles) = body.into_any_view().collect_styles(true).await;
+            let head = head
+                .into_iter()
+                .fold(AnyView::new(()), |head, view| head.appended(view));
+            crate::view![
+                h::doctype(),
+                UpdateView::content_only_on_update(Html::<marker::Html, _>::new(
+                    "html",
+                    HtmlAttributes { lang, dir },
+                    crate::view![
+                        h::head![
+                            charset.map(|charset| h::meta(()).charset(charset).into_void_element()),
+                            viewport.map(|viewport| h::meta(())
+                                .name("viewport")
+                                .content(viewport)
+                                .into_void_element()),
+                            favicon.map(|href| h::link().rel(Rel::Icon).href(href)),
+                            crate::server::scripts(livereload),
+                            head,
+                            styles,
+                        ],
+                        body,
+                    ],
+                )),
+            ]
+        }
+        .into_any_view()
+        .render(r)
+    }
+}
+
+impl Style for Document {
+    fn style_mut(&mut self) -> &mut StyleDefinition {
+        self.body_style.get_or_insert_default().style_mut()
+    }
+}
+
+impl SubStyle for Document {
+    fn style_mut_for(&mut self, modifier: StyleModifier) -> &mut StyleDefinition {
+        self.body_style
+            .get_or_insert_default()
+            .style_mut_for(modifier)
+    }
+
+    fn substyle<F: for<'a> FnOnce(StyleDelegate<'a>) -> StyleDelegate<'a>>(
+        mut self,
+        modifier: StyleModifier,
+        f: F,
+    ) -> Self {
+        let style = self
+            .body_style
+            .take()
+            .unwrap_or_default()
+            .substyle(modifier, f);
+        self.body_style = Some(style);
+        self
+    }
+}
+
+mod marker {
+    pub struct Html;
+    pub struct Body;
+}
+
+struct HtmlAttributes {
+    lang: Option<Cow<'static, str>>,
+    dir: Option<Dir>,
+}
+
+impl Attributes for HtmlAttributes {
+    fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
+        if let Some(lang) = self.lang {
+            r.attribute("lang", lang);
+        }
+        if let Some(dir) = self.dir {
+            r.attribute("dir", dir);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scope::Scope;
+
+    async fn render(view: impl View) -> String {
+        let scope = Scope::new(false, true);
+        let r = scope.create_renderer();
+        scope.run(view.render(r)).await.unwrap().end().unwrap().html
+    }
+
+    #[tokio::test]
+    async fn test_document() {
+        let html = render(
+            document(h::p(h::text!("content")))
+                .lang("en")
+                .dir(Dir::Ltr)
+                .favicon("/favicon.ico")
+                .head(h::title("Title"))
+                .body_class("a")
+                .body_class("b"),
+        )
+        .await;
+        assert!(html.starts_with(
+            r#"<!DOCTYPE html><html lang="en" dir="ltr"><head><meta charset="utf-8"/><meta content="width=device-width, initial-scale=1" name="viewport"/><link href="/favicon.ico" rel="icon"/><script "#
+        ));
+        assert!(html.contains("<title>Title</title>"));
+        assert!(html.contains(r#"<body class="a b"><p "#));
+        assert!(html.ends_with("content</p></body></html>"));
+        assert_eq!(
+            html.contains("/livereload.js"),
+            cfg!(feature = "livereload")
+        );
+
+        let html = render(document(()).charset(None).viewport(None)).await;
+        assert!(!html.contains("<meta"));
+    }
+
+    #[cfg(feature = "livereload")]
+    #[tokio::test]
+    async fn test_document_without_livereload() {
+        let html = render(document(()).livereload(false)).await;
+        assert!(!html.contains("/livereload.js"));
+    }
+
+    #[tokio::test]
+    async fn test_basic_document() {
+        let html = render(crate::server::basic_document(h::p(h::text!("content")))).await;
+        assert!(html.starts_with("<!DOCTYPE html><html><head><script "));
+        assert!(!html.contains("<meta"));
+        assert!(html.ends_with("<body><p>content</p></body></html>"));
+    }
+}
@@ -109,6 +109,9 @@ pub enum InternalError {
     InvalidAttributeName {
         name: String,
     },
+    AttributeAfterContent {
+        name: String,
+    },
     InvalidHeaderValue(http::header::InvalidHeaderValue),
     Join(tokio::task::JoinError),
     MissingBoundaryAttribute,
@@ -157,6 +160,7 @@ impl error::Error for InternalError {
         match self {
             Self::Render
             | Self::InvalidAttributeName { .. }
+            | Self::AttributeAfterContent { .. }
             | Self::MissingBoundaryAttribute
             | Self::FutureCompleted => None,
             Self::Serialize { err, .. } => Some(err),
@@ -177,6 +181,9 @@ impl fmt::Display for InternalError {
             Self::InvalidAttributeName { name } => {
                 write!(f, "invalid attribute name `{name}`")
             }
+            Self::AttributeAfterContent { name } => {
+                write!(f, "attribute `{name}` written after the element's content")
+            }
             Self::InvalidHeaderValue { .. } => {
                 write!(f, "invalid header value")
             }
@@ -6,11 +6,16 @@ use http_error::HttpError;
 use crate::View;
 use crate::error::InternalError;
 use crate::event::Event;
+use crate::render::json_header_value;
 
+pub(crate) const HEADER_NAME: HeaderName = HeaderName::from_static("cabin-event");
+
+/// Fire an event on the client after the response was applied. Multiple events are dispatched in
+/// order, each bubbling up from the updated boundary to the first ancestor boundary that handles
+/// it.
 #[derive(Debug)]
 pub struct FireEvent {
-    event_id: HeaderValue,
-    payload: HeaderValue,
+    value: HeaderValue,
 }
 
 impl FireEvent {
@@ -18,30 +23,32 @@ impl FireEvent {
     where
         E: serde::Serialize + Event + Send + 'static,
     {
+        #[derive(serde::Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Fired<E> {
+            event_id: &'static str,
+            payload: E,
+        }
+
+        let json = serde_json::to_string(&Fired {
+            event_id: E::ID,
+            payload: event,
+        })
+        .map_err(|err| InternalError::Serialize {
+            what: "fire event".into(),
+            err,
+        })?;
         Ok(Self {
-            event_id: HeaderValue::from_static(E::ID),
-            payload: HeaderValue::from_str(&serde_json::to_string(&event).map_err(|err| {
-                InternalError::Serialize {
-                    what: "fire event".into(),
-                    err,
-                }
-            })?)
-            .map_err(InternalError::InvalidHeaderValue)?,
+            value: json_header_value(&json)?,
         })
     }
 }
 
 impl View for FireEvent {
     fn render(self, mut r: crate::render::Renderer) -> crate::view::RenderFuture {
-        let headers = r.headers_mut();
-        headers.insert(
-            HeaderName::from_static("cabin-event"),
-            self.event_id.clone(),
-        );
-        headers.insert(
-            HeaderName::from_static("cabin-event-payload"),
-            self.payload.clone(),
-        );
+        // Multiple events are sent as multiple header values (joined by commas by the client),
+        // which is why they are appended.
+        r.headers_mut().append(HEADER_NAME, self.value);
         crate::view::RenderFuture::Ready(Ok(r))
     }
 }
@@ -52,16 +59,7 @@ impl HttpError for FireEvent {
     }
 
     fn headers(&self) -> Option<Vec<(HeaderName, HeaderValue)>> {
-        Some(vec![
-            (
-                HeaderName::from_static("cabin-event"),
-                self.event_id.clone(),
-            ),
-            (
-                HeaderName::from_static("cabin-event-payload"),
-                self.payload.clone(),
-            ),
-        ])
+        Some(vec![(HEADER_NAME, self.value.clone())])
     }
 }
 
@@ -78,3 +76,58 @@ impl From<FireEvent> for Box<dyn HttpError + Send + 'static> {
         Box::new(err)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Renderer;
+
+    #[tokio::test]
+    async fn test_multiple_events() {
+        let out = crate::view![
+            FireEvent::new(1usize).unwrap(),
+            FireEvent::new("ok").unwrap()
+        ]
+        .render(Renderer::new(true, true))
+        .await
+        .unwrap()
+        .end()
+        .unwrap();
+        assert_eq!(
+            out.headers
+                .get_all(HEADER_NAME)
+                .iter()
+                .map(|v| v.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                r#"{"eventId":"usize","payload":1}"#,
+                r#"{"eventId":"str","payload":"ok"}"#,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_events_order() {
+        let mut r = FireEvent::new(1usize)
+            .unwrap()
+            .render(Renderer::new(true, true))
+            .await
+            .unwrap();
+        // a child renderer whose header map grew larger before (e.g. from the renderer pool)
+        let mut child = Renderer::new(true, true);
+        child.headers_mut().reserve(64);
+        r.append(FireEvent::new("ok").unwrap().render(child).await.unwrap());
+        let out = r.end().unwrap();
+        assert_eq!(
+            out.headers
+                .get_all(HEADER_NAME)
+                .iter()
+                .map(|v| v.to_str().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                r#"{"eventId":"usize","payload":1}"#,
+                r#"{"eventId":"str","payload":"ok"}"#,
+            ]
+        );
+    }
+}
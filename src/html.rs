@@ -1,8 +1,11 @@
 pub mod attributes;
 pub mod elements;
 pub mod events;
+pub mod keyboard;
 pub mod list;
 mod raw;
+#[cfg(feature = "sanitize")]
+pub mod sanitize;
 
 use std::borrow::Cow;
 use std::marker::PhantomData;
@@ -71,6 +74,8 @@ pub mod h {
     #[doc(inline)]
     pub use super::elements::colgroup::{self as colgroup, Colgroup, colgroup};
     #[doc(inline)]
+    pub use super::elements::custom_element::custom_element;
+    #[doc(inline)]
     pub use super::elements::data::{self as data, Data, data};
     #[doc(inline)]
     pub use super::elements::datalist::datalist;
@@ -253,6 +258,8 @@ pub mod h {
     #[doc(inline)]
     pub use super::elements::wbr::wbr;
     #[doc(inline)]
+    pub use super::keyboard::shortcut;
+    #[doc(inline)]
     pub use crate::html::raw;
     #[doc(inline)]
     #[doc(inline)]
@@ -61,3 +61,18 @@ impl Attributes for (&'static str, Cow<'static, str>) {
         Pair::new(attr, self)
     }
 }
+
+/// Whether the given `name` is a valid HTML attribute name.
+/// <https://html.spec.whatwg.org/multipage/syntax.html#attributes-2>
+pub(crate) fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|ch| {
+            ch.is_control()
+                || matches!(ch, ' ' | '"' | '\'' | '>' | '/' | '=' | '<')
+                || is_noncharacter(ch)
+        })
+}
+
+fn is_noncharacter(ch: char) -> bool {
+    matches!(ch, '\u{FDD0}'..='\u{FDEF}') || (ch as u32 & 0xFFFE) == 0xFFFE
+}
@@ -10,6 +10,7 @@ pub mod canvas;
 pub mod col;
 pub mod colgroup;
 pub mod common;
+pub mod custom_element;
 pub mod data;
 pub mod del;
 pub mod details;
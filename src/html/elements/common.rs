@@ -1,11 +1,13 @@
 use std::borrow::Cow;
 use std::fmt;
+use std::time::Duration;
 
 use cabin_macros::Attribute;
 
 use crate::event::Event;
 use crate::html::attributes::{Attributes, WithAttribute};
 use crate::html::events::CustomEvent;
+use crate::html::keyboard::{KeyEvent, KeyFilter};
 
 pub trait Common: WithAttribute {
     /// Unique identifier across the document.
@@ -63,6 +65,127 @@ pub trait Common: WithAttribute {
     {
         self.with_attribute(OnAnimationEnd(CustomEvent::new("animationend", event)))
     }
+
+    /// Delay firing events of this element until no further event occurred for the given
+    /// `duration` (e.g. for search-as-you-type). Overrides the default debounce of `500ms` of
+    /// input events. A duration of zero disables debouncing.
+    fn debounce(self, duration: Duration) -> Self::Output<Debounce> {
+        self.with_attribute(Debounce(duration))
+    }
+
+    /// Fire events of this element at most once per `duration`. Events in between are dropped.
+    fn throttle(self, duration: Duration) -> Self::Output<Throttle> {
+        self.with_attribute(Throttle(duration))
+    }
+
+    /// Whether the browser's default action (e.g. following a link) should be prevented when an
+    /// event of this element is fired. Click and submit events prevent the default by default.
+    /// Submit events always prevent the default (`false` is ignored for them), as the form would
+    /// otherwise be submitted twice.
+    fn prevent_default(self, prevent_default: bool) -> Self::Output<PreventDefault> {
+        self.with_attribute(PreventDefault(prevent_default))
+    }
+
+    /// Ask the user to confirm (via the browser's native `confirm()` dialog) before firing events
+    /// of this element. Debounced or throttled events are only confirmed once they are about to
+    /// fire.
+    fn confirm(self, message: impl Into<Cow<'static, str>>) -> Self::Output<Confirm> {
+        self.with_attribute(Confirm::Message(message.into()))
+    }
+
+    /// Ask the user to confirm via the server-rendered `<dialog>` with the given `id` before
+    /// firing events of this element. The dialog is opened as modal, and the event is only fired
+    /// if it is closed with the return value `confirm` (e.g. via a
+    /// `<button value="confirm">` inside of a `<form method="dialog">`).
+    fn confirm_with_dialog(self, id: impl Into<Cow<'static, str>>) -> Self::Output<Confirm> {
+        self.with_attribute(Confirm::Dialog(id.into()))
+    }
+
+    /// Only fire events of this element once (until the page is reloaded).
+    fn once(self) -> Self::Output<Once> {
+        self.with_attribute(Once(true))
+    }
+
+    /// Fire `event` when the user starts dragging the element (or one of its descendants). The
+    /// element must be draggable (see [crate::html::Global::draggable]).
+    fn on_drag_start<E>(self, event: E) -> Self::Output<OnDragStart<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnDragStart(CustomEvent::new("dragstart", event)))
+    }
+
+    /// Fire `event` when something is dropped onto the element (or one of its descendants). Use
+    /// [crate::html::events::DraggedKey] and [crate::html::events::DropKey] to receive the keys of
+    /// the involved [crate::view::IteratorExt::keyed] items.
+    fn on_drop<E>(self, event: E) -> Self::Output<OnDrop<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnDrop(CustomEvent::new("drop", event)))
+    }
+
+    /// Fire `event` when the element (or one of its descendants) receives focus.
+    fn on_focus<E>(self, event: E) -> Self::Output<OnFocus<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnFocus(CustomEvent::new("focus", event)))
+    }
+
+    /// Fire `event` when the element (or one of its descendants) loses focus.
+    fn on_blur<E>(self, event: E) -> Self::Output<OnBlur<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnBlur(CustomEvent::new("blur", event)))
+    }
+
+    /// Fire `event` when the element enters the viewport. Useful for lazy-loaded sections or
+    /// infinite scroll (render a sentinel element with `on_visible` after the last item, and
+    /// append the next page once it fires).
+    fn on_visible<E>(self, event: E) -> Self::Output<OnVisible<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnVisible(CustomEvent::new("visible", event)))
+    }
+
+    /// Fire `event` when the element leaves the viewport (after it was visible before).
+    fn on_hidden<E>(self, event: E) -> Self::Output<OnHidden<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnHidden(CustomEvent::new("hidden", event)))
+    }
+
+    /// Fire `event` when a key matching `filter` is pressed while the element (or one of its
+    /// descendants) has focus, e.g. `.on_key_down(Key::Enter.with_ctrl(), Save)`. Use
+    /// [crate::html::events::InputKey] to receive the pressed key. The browser's default action
+    /// of a matching key is prevented (also on inputs, e.g. to not submit the form on Enter),
+    /// except for `Key::Any` filters (so typing keeps working). Keydowns of `Key::Any` filters
+    /// still reach document-wide [crate::html::keyboard::shortcut]s.
+    fn on_key_down<E>(self, filter: impl Into<KeyFilter>, event: E) -> Self::Output<OnKeyDown<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnKeyDown(KeyEvent {
+            event: CustomEvent::new("keydown", event),
+            filter: filter.into(),
+        }))
+    }
+
+    /// Fire `event` when a key matching `filter` is released while the element (or one of its
+    /// descendants) has focus.
+    fn on_key_up<E>(self, filter: impl Into<KeyFilter>, event: E) -> Self::Output<OnKeyUp<E>>
+    where
+        E: serde::Serialize + Event + Send + 'static,
+    {
+        self.with_attribute(OnKeyUp(KeyEvent {
+            event: CustomEvent::new("keyup", event),
+            filter: filter.into(),
+        }))
+    }
 }
 
 /// Unique identifier across the document.
@@ -83,6 +206,63 @@ impl Attributes for Class {
     }
 }
 
+/// Debounce events of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Debounce(pub Duration);
+
+impl Attributes for Debounce {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        r.attribute("cabin-debounce", self.0.as_millis());
+        Ok(())
+    }
+}
+
+/// Throttle events of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Throttle(pub Duration);
+
+impl Attributes for Throttle {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        r.attribute("cabin-throttle", self.0.as_millis());
+        Ok(())
+    }
+}
+
+/// Whether to prevent the default action of events of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PreventDefault(pub bool);
+
+impl Attributes for PreventDefault {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        r.attribute("cabin-prevent-default", self.0);
+        Ok(())
+    }
+}
+
+/// Ask for confirmation before firing events of the element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Confirm {
+    /// Use the browser's native `confirm()` dialog with the given message.
+    Message(Cow<'static, str>),
+    /// Use the `<dialog>` with the given id.
+    Dialog(Cow<'static, str>),
+}
+
+impl Attributes for Confirm {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        match self {
+            Confirm::Message(message) => r.attribute("cabin-confirm", message),
+            Confirm::Dialog(id) => r.attribute("cabin-confirm-dialog", id),
+        }
+        Ok(())
+    }
+}
+
+/// Only fire events of the element once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+#[attribute(name = "cabin-once")]
+pub struct Once(pub bool);
+
 pub struct OnClick<E>(CustomEvent<E>);
 
 impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnClick<E> {
@@ -115,6 +295,70 @@ impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnAnimationEnd
     }
 }
 
+pub struct OnDragStart<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnDragStart<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnDrop<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnDrop<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnFocus<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnFocus<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnBlur<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnBlur<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnVisible<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnVisible<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnHidden<E>(CustomEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnHidden<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnKeyDown<E>(KeyEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnKeyDown<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
+pub struct OnKeyUp<E>(KeyEvent<E>);
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for OnKeyUp<E> {
+    fn render(self, r: &mut crate::render::ElementRenderer) -> Result<(), crate::Error> {
+        self.0.render(r)
+    }
+}
+
 impl Class {
     pub fn append(self, other: Class) -> Class {
         if self.0.is_empty() {
@@ -163,3 +407,60 @@ impl fmt::Display for Class {
         self.0.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::View;
+    use crate::html::h::button;
+    use crate::render::Renderer;
+
+    #[tokio::test]
+    async fn test_event_modifiers() {
+        assert_eq!(
+            button(())
+                .on_click(())
+                .debounce(Duration::from_millis(300))
+                .throttle(Duration::from_secs(1))
+                .prevent_default(false)
+                .once()
+                .confirm("Sure?")
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<button cabin-confirm="Sure?" cabin-once cabin-prevent-default="false" cabin-throttle="1000" cabin-debounce="300" cabin-click="()" cabin-click-payload="null"></button>"#
+        );
+        assert_eq!(
+            button(())
+                .confirm_with_dialog("confirm-delete")
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<button cabin-confirm-dialog="confirm-delete"></button>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_focus_and_visibility_events() {
+        assert_eq!(
+            button(())
+                .on_focus(1usize)
+                .on_blur(2usize)
+                .on_visible(3usize)
+                .on_hidden(4usize)
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<button cabin-hidden="usize" cabin-hidden-payload="4" cabin-visible="usize" cabin-visible-payload="3" cabin-blur="usize" cabin-blur-payload="2" cabin-focus="usize" cabin-focus-payload="1"></button>"#
+        );
+    }
+}
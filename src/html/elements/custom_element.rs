@@ -0,0 +1,100 @@
+use super::common::Common;
+use super::global::Global;
+use crate::View;
+use crate::html::attributes::Attributes;
+use crate::html::{Aria, Html};
+
+/// An autonomous custom element (e.g. a web component). The `name` must start with a lowercase
+/// ASCII letter, and contain a hyphen (e.g. `my-widget`). Use [Global::attr] to set its
+/// attributes.
+///
+/// # Panics
+///
+/// Panics if the `name` is not a valid custom element name.
+pub fn custom_element(name: &'static str, content: impl View) -> Html<marker::CustomElement, ()> {
+    assert!(
+        is_valid_custom_element_name(name),
+        "invalid custom element name `{name}`"
+    );
+    Html::new(name, (), content)
+}
+
+pub mod marker {
+    pub struct CustomElement;
+}
+
+impl<A: Attributes> Common for Html<marker::CustomElement, A> {}
+impl<A: Attributes> Global for Html<marker::CustomElement, A> {}
+impl<A: Attributes> Aria for Html<marker::CustomElement, A> {}
+
+/// <https://html.spec.whatwg.org/multipage/custom-elements.html#valid-custom-element-name>
+fn is_valid_custom_element_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.contains('-')
+        && !name.contains(|c: char| {
+            c.is_ascii_uppercase() || c.is_ascii_whitespace() || matches!(c, '\0' | '/' | '>')
+        })
+        && !matches!(
+            name,
+            "annotation-xml"
+                | "color-profile"
+                | "font-face"
+                | "font-face-src"
+                | "font-face-uri"
+                | "font-face-format"
+                | "font-face-name"
+                | "missing-glyph"
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::Renderer;
+
+    #[tokio::test]
+    async fn test_custom_element_attributes() {
+        assert_eq!(
+            custom_element("my-widget", "hi")
+                .data("row-id", "42")
+                .attr("theme", "dark")
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<my-widget data-row-id="42" theme="dark">hi</my-widget>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_attribute_name() {
+        let Err(err) = custom_element("my-widget", ())
+            .attr("onclick=\"alert(1)\"", "")
+            .render(Renderer::new(false, true))
+            .await
+        else {
+            panic!("expected invalid attribute name error");
+        };
+        assert_eq!(
+            err.to_string(),
+            r#"invalid attribute name `onclick="alert(1)"`"#
+        );
+    }
+
+    #[test]
+    fn test_custom_element_name() {
+        assert!(is_valid_custom_element_name("my-widget"));
+        assert!(!is_valid_custom_element_name("widget"));
+        assert!(!is_valid_custom_element_name("My-widget"));
+        assert!(!is_valid_custom_element_name("font-face"));
+        assert!(!is_valid_custom_element_name("my-widget><script"));
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid custom element name `my widget`")]
+    fn test_invalid_custom_element_name() {
+        custom_element("my widget", ());
+    }
+}
@@ -3,7 +3,9 @@ use std::fmt;
 
 use cabin_macros::Attribute;
 
-use crate::html::attributes::WithAttribute;
+use crate::error::InternalError;
+use crate::html::attributes::{Attributes, WithAttribute, is_valid_attribute_name};
+use crate::render::ElementRenderer;
 
 pub trait Global: WithAttribute {
     /// Used by the user agent as a guide for creating a keyboard shortcut that activates or
@@ -12,6 +14,22 @@ pub trait Global: WithAttribute {
         self.with_attribute(AccessKey(access_key.into()))
     }
 
+    /// Set an arbitrary attribute (e.g. an attribute of a custom element or third-party widget).
+    /// Can be called multiple times to set multiple attributes. Invalid attribute names fail the
+    /// render with [crate::error::InternalError::InvalidAttributeName].
+    fn attr(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<CustomAttributes> {
+        let mut attrs = self
+            .get_attribute_mut::<CustomAttributes>()
+            .map(std::mem::take)
+            .unwrap_or_default();
+        attrs.0.push((name.into(), value.into()));
+        self.with_attribute(attrs)
+    }
+
     /// Hints the user-agent of how to automatically capitalize input (from non-physical
     fn auto_capitalize(self, auto_capitalize: AutoCapitalize) -> Self::Output<AutoCapitalize> {
         self.with_attribute(auto_capitalize)
@@ -37,6 +55,16 @@ pub trait Global: WithAttribute {
         self.with_attribute(ContentEditable(content_editable))
     }
 
+    /// Set a custom `data-{name}` attribute. Can be called multiple times to set multiple
+    /// attributes.
+    fn data(
+        self,
+        name: impl AsRef<str>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<CustomAttributes> {
+        self.attr(format!("data-{}", name.as_ref()), value)
+    }
+
     /// The element's text directionality.
     fn dir(self, dir: Dir) -> Self::Output<Dir> {
         self.with_attribute(dir)
@@ -175,6 +203,10 @@ pub trait Global: WithAttribute {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
 pub struct AccessKey(pub Cow<'static, str>);
 
+/// Arbitrary attributes (e.g. `data-*` attributes or attributes of custom elements).
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomAttributes(pub Vec<(Cow<'static, str>, Cow<'static, str>)>);
+
 /// Indicate that the element is to be focused as soon as the page is loaded.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
 pub struct AutoFocus(pub bool);
@@ -431,3 +463,18 @@ impl fmt::Display for Translate {
         Ok(())
     }
 }
+
+impl Attributes for CustomAttributes {
+    fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
+        for (name, value) in self.0 {
+            if !is_valid_attribute_name(&name) {
+                return Err(InternalError::InvalidAttributeName {
+                    name: name.into_owned(),
+                }
+                .into());
+            }
+            r.attribute(&name, value);
+        }
+        Ok(())
+    }
+}
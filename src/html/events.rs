@@ -7,33 +7,34 @@ use serde::{Deserialize, Serialize};
 use super::attributes::Attributes;
 use crate::event::Event;
 
-#[derive(Debug, Hash)]
-pub struct InputValue(Cow<'static, str>);
+/// Implements [Serialize] for a placeholder type by serializing the placeholder string, which is
+/// replaced with the actual value by the client before the event is sent to the server.
+macro_rules! placeholder {
+    ($ty:ident, $placeholder:literal) => {
+        impl Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str($placeholder)
+            }
+        }
 
-impl Serialize for InputValue {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        serializer.serialize_str("_##InputValue")
-    }
+        impl $ty {
+            pub fn placeholder() -> Self {
+                Self::default()
+            }
+        }
+    };
 }
 
-impl<'de> Deserialize<'de> for InputValue {
-    fn deserialize<D>(deserializer: D) -> Result<InputValue, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let value: Cow<'static, str> = Deserialize::deserialize(deserializer)?;
-        Ok(InputValue(value))
-    }
-}
+/// Placeholder for the value of an `<input>`, `<textarea>` or `<select>`.
+#[derive(Debug, Default, Hash, Deserialize)]
+pub struct InputValue(Cow<'static, str>);
 
-impl InputValue {
-    pub fn placeholder() -> Self {
-        Self(Cow::Borrowed(""))
-    }
+placeholder!(InputValue, "_##InputValue");
 
+impl InputValue {
     pub fn take(self) -> Cow<'static, str> {
         self.0
     }
@@ -65,50 +66,307 @@ impl fmt::Display for InputValue {
     }
 }
 
-#[derive(Debug, Hash)]
+/// Placeholder for the checked state of a checkbox or radio `<input>`.
+#[derive(Debug, Default, Hash, Deserialize)]
 pub struct InputChecked(bool);
 
-impl Serialize for InputChecked {
+placeholder!(InputChecked, "_##InputChecked");
+
+impl InputChecked {
+    pub fn take(self) -> bool {
+        self.0
+    }
+}
+
+impl From<InputChecked> for bool {
+    fn from(v: InputChecked) -> Self {
+        v.0
+    }
+}
+
+impl fmt::Display for InputChecked {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Placeholder for the pressed key of a keyboard event (see [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)).
+#[derive(Debug, Default, Hash, Deserialize)]
+pub struct InputKey(Cow<'static, str>);
+
+placeholder!(InputKey, "_##InputKey");
+
+impl InputKey {
+    pub fn take(self) -> Cow<'static, str> {
+        self.0
+    }
+}
+
+impl Deref for InputKey {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for InputKey {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl fmt::Display for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Placeholder for the values of all selected options of a `<select multiple>`.
+#[derive(Debug, Default, Hash, Deserialize)]
+pub struct SelectedValues(Vec<String>);
+
+placeholder!(SelectedValues, "_##SelectedValues");
+
+impl SelectedValues {
+    pub fn take(self) -> Vec<String> {
+        self.0
+    }
+}
+
+impl Deref for SelectedValues {
+    type Target = [String];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Placeholder for the text selection range of an `<input>` or `<textarea>`.
+#[derive(Debug, Default, Clone, Copy, Hash, Deserialize)]
+pub struct SelectionRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+placeholder!(SelectionRange, "_##SelectionRange");
+
+/// Placeholder for the pointer position of a mouse/pointer event (e.g. a click).
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerPosition {
+    /// Position relative to the viewport.
+    pub client_x: f64,
+    pub client_y: f64,
+    /// Position relative to the element the event occurred on.
+    pub offset_x: f64,
+    pub offset_y: f64,
+}
+
+placeholder!(PointerPosition, "_##PointerPosition");
+
+/// Placeholder for the scroll position of the element that defines the event.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+pub struct ScrollPosition {
+    pub x: f64,
+    pub y: f64,
+}
+
+placeholder!(ScrollPosition, "_##ScrollPosition");
+
+/// Placeholder for the metadata of the files selected in an `<input type="file">` (the contents
+/// are only sent on form submit).
+#[derive(Debug, Default, Hash, Deserialize)]
+pub struct Files(Vec<FileInfo>);
+
+placeholder!(Files, "_##Files");
+
+impl Files {
+    pub fn take(self) -> Vec<FileInfo> {
+        self.0
+    }
+}
+
+impl Deref for Files {
+    type Target = [FileInfo];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileInfo {
+    pub name: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// MIME type (empty if unknown).
+    #[serde(rename = "type")]
+    pub mime_type: String,
+    /// Milliseconds since the UNIX epoch.
+    pub last_modified: u64,
+}
+
+/// Placeholder for a `data-*` attribute of the element that defines the event (`None` if the
+/// attribute is not set).
+#[derive(Debug, Hash)]
+pub struct DataValue {
+    name: Cow<'static, str>,
+    value: Option<String>,
+}
+
+impl DataValue {
+    /// The `name` is without the `data-` prefix (e.g. `row-id` for `data-row-id`).
+    pub fn placeholder(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+        }
+    }
+
+    pub fn take(self) -> Option<String> {
+        self.value
+    }
+}
+
+impl Serialize for DataValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str("_##InputChecked")
+        serializer.collect_str(&format_args!("_##data:{}", self.name))
     }
 }
 
-impl<'de> Deserialize<'de> for InputChecked {
-    fn deserialize<D>(deserializer: D) -> Result<InputChecked, D::Error>
+impl<'de> Deserialize<'de> for DataValue {
+    fn deserialize<D>(deserializer: D) -> Result<DataValue, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let value: bool = Deserialize::deserialize(deserializer)?;
-        Ok(InputChecked(value))
+        Ok(DataValue {
+            name: Cow::Borrowed(""),
+            value: Deserialize::deserialize(deserializer)?,
+        })
     }
 }
 
-impl InputChecked {
-    pub fn placeholder() -> Self {
-        Self(false)
+/// Placeholder for the value of another named control of the form of the element that defines
+/// the event (`None` if there is no such control).
+#[derive(Debug, Hash)]
+pub struct FormValue {
+    name: Cow<'static, str>,
+    value: Option<String>,
+}
+
+impl FormValue {
+    pub fn placeholder(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            value: None,
+        }
     }
 
-    pub fn take(self) -> bool {
-        self.0
+    pub fn take(self) -> Option<String> {
+        self.value
     }
 }
 
-impl From<InputChecked> for bool {
-    fn from(v: InputChecked) -> Self {
-        v.0
+impl Serialize for FormValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(&format_args!("_##form:{}", self.name))
     }
 }
 
-impl fmt::Display for InputChecked {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.0.fmt(f)
+impl<'de> Deserialize<'de> for FormValue {
+    fn deserialize<D>(deserializer: D) -> Result<FormValue, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(FormValue {
+            name: Cow::Borrowed(""),
+            value: Deserialize::deserialize(deserializer)?,
+        })
     }
 }
 
+/// Placeholder for the key of the [crate::view::IteratorExt::keyed] item that is being dragged
+/// (`None` if the dragged element is not a keyed item).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DraggedKey<K>(KeyPlaceholder<K>);
+
+/// Placeholder for the key of the [crate::view::IteratorExt::keyed] item something was dropped
+/// onto (`None` if the drop target is not within a keyed item).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DropKey<K>(KeyPlaceholder<K>);
+
+/// Unlike other placeholders, key placeholders serialize their actual key once they are resolved
+/// (e.g. when they are part of a [crate::view::Reorder] that is sent back to the client).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum KeyPlaceholder<K> {
+    Placeholder,
+    Resolved(Option<K>),
+}
+
+macro_rules! key_placeholder {
+    ($ty:ident, $placeholder:literal) => {
+        impl<K: Serialize> Serialize for $ty<K> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                match &self.0 {
+                    KeyPlaceholder::Placeholder => serializer.serialize_str($placeholder),
+                    KeyPlaceholder::Resolved(key) => key.serialize(serializer),
+                }
+            }
+        }
+
+        impl<'de, K: Deserialize<'de>> Deserialize<'de> for $ty<K> {
+            fn deserialize<D>(deserializer: D) -> Result<$ty<K>, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                Ok($ty(KeyPlaceholder::Resolved(Deserialize::deserialize(
+                    deserializer,
+                )?)))
+            }
+        }
+
+        impl<K> $ty<K> {
+            pub fn placeholder() -> Self {
+                Self(KeyPlaceholder::Placeholder)
+            }
+
+            /// An already resolved key.
+            pub fn new(key: Option<K>) -> Self {
+                Self(KeyPlaceholder::Resolved(key))
+            }
+
+            /// The key (`None` for placeholders that were not resolved yet).
+            pub fn get(&self) -> Option<&K> {
+                match &self.0 {
+                    KeyPlaceholder::Placeholder => None,
+                    KeyPlaceholder::Resolved(key) => key.as_ref(),
+                }
+            }
+
+            pub fn take(self) -> Option<K> {
+                match self.0 {
+                    KeyPlaceholder::Placeholder => None,
+                    KeyPlaceholder::Resolved(key) => key,
+                }
+            }
+        }
+    };
+}
+
+key_placeholder!(DraggedKey, "_##DraggedKey");
+key_placeholder!(DropKey, "_##DropKey");
+
 pub struct CustomEvent<E> {
     pub(crate) name: Cow<'static, str>,
     pub(crate) event: E,
@@ -128,3 +386,27 @@ impl<E: serde::Serialize + Event + Send + 'static> Attributes for CustomEvent<E>
         r.event_attributes(self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Select(SelectedValues, DataValue);
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            serde_json::to_string(&Select(
+                SelectedValues::placeholder(),
+                DataValue::placeholder("row-id")
+            ))
+            .unwrap(),
+            r#"["_##SelectedValues","_##data:row-id"]"#
+        );
+
+        let Select(values, data) = serde_json::from_str(r#"[["a","b"],"42"]"#).unwrap();
+        assert_eq!(values.take(), ["a", "b"]);
+        assert_eq!(data.take().as_deref(), Some("42"));
+    }
+}
@@ -0,0 +1,261 @@
+//! Keyboard event filters, used by [super::Common::on_key_down], [super::Common::on_key_up] and
+//! [shortcut].
+
+use std::borrow::Cow;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use super::Html;
+use super::attributes::Attributes;
+use super::events::CustomEvent;
+use crate::View;
+use crate::event::Event;
+use crate::render::ElementRenderer;
+
+/// A key as reported by [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Matches any key.
+    Any,
+    Enter,
+    Escape,
+    Tab,
+    Space,
+    Backspace,
+    Delete,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// A printable character (matched case-insensitively, e.g. `Key::Char('k')` also matches
+    /// `K`).
+    Char(char),
+    /// Any other key value (e.g. `F1`).
+    Named(Cow<'static, str>),
+}
+
+/// A [Key] together with the modifier keys that must be held down. Modifiers that are not
+/// requested must not be pressed either (except for `shift` in combination with
+/// [Key::Char], as it is often required to type the character in the first place).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct KeyFilter {
+    key: Key,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    ctrl: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    shift: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    alt: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    meta: bool,
+}
+
+/// Register a document-wide keyboard shortcut that fires `event` when the `filter` matches. The
+/// event is handled by the surrounding boundary (if any). Shortcuts without a `ctrl`, `alt` or
+/// `meta` modifier are ignored while the focus is within an editable element.
+pub fn shortcut<E>(filter: impl Into<KeyFilter>, event: E) -> impl View
+where
+    E: serde::Serialize + Event + Send + 'static,
+{
+    Html::<marker::Shortcut, _>::new(
+        "cabin-shortcut",
+        KeyEvent {
+            event: CustomEvent::new("keydown", event),
+            filter: filter.into(),
+        },
+        (),
+    )
+}
+
+pub mod marker {
+    pub struct Shortcut;
+}
+
+impl Key {
+    /// Require the `Control` key to be held down.
+    pub fn with_ctrl(self) -> KeyFilter {
+        KeyFilter::from(self).with_ctrl()
+    }
+
+    /// Require the `Shift` key to be held down.
+    pub fn with_shift(self) -> KeyFilter {
+        KeyFilter::from(self).with_shift()
+    }
+
+    /// Require the `Alt` (`Option` on macOS) key to be held down.
+    pub fn with_alt(self) -> KeyFilter {
+        KeyFilter::from(self).with_alt()
+    }
+
+    /// Require the `Meta` (`Command` on macOS) key to be held down.
+    pub fn with_meta(self) -> KeyFilter {
+        KeyFilter::from(self).with_meta()
+    }
+}
+
+impl KeyFilter {
+    /// Require the `Control` key to be held down.
+    pub fn with_ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Require the `Shift` key to be held down.
+    pub fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    /// Require the `Alt` (`Option` on macOS) key to be held down.
+    pub fn with_alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Require the `Meta` (`Command` on macOS) key to be held down.
+    pub fn with_meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+}
+
+impl From<Key> for KeyFilter {
+    fn from(key: Key) -> Self {
+        KeyFilter {
+            key,
+            ctrl: false,
+            shift: false,
+            alt: false,
+            meta: false,
+        }
+    }
+}
+
+impl From<char> for KeyFilter {
+    fn from(c: char) -> Self {
+        Key::Char(c).into()
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Key::Any => Ok(()),
+            Key::Enter => f.write_str("Enter"),
+            Key::Escape => f.write_str("Escape"),
+            Key::Tab => f.write_str("Tab"),
+            Key::Space => f.write_str(" "),
+            Key::Backspace => f.write_str("Backspace"),
+            Key::Delete => f.write_str("Delete"),
+            Key::ArrowUp => f.write_str("ArrowUp"),
+            Key::ArrowDown => f.write_str("ArrowDown"),
+            Key::ArrowLeft => f.write_str("ArrowLeft"),
+            Key::ArrowRight => f.write_str("ArrowRight"),
+            Key::Home => f.write_str("Home"),
+            Key::End => f.write_str("End"),
+            Key::PageUp => f.write_str("PageUp"),
+            Key::PageDown => f.write_str("PageDown"),
+            Key::Char(c) => fmt::Display::fmt(c, f),
+            Key::Named(name) => f.write_str(name),
+        }
+    }
+}
+
+impl Serialize for Key {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Key::Any => serializer.serialize_none(),
+            key => serializer.collect_str(key),
+        }
+    }
+}
+
+/// A keyboard event handler together with its [KeyFilter].
+pub struct KeyEvent<E> {
+    pub(crate) event: CustomEvent<E>,
+    pub(crate) filter: KeyFilter,
+}
+
+impl<E: serde::Serialize + Event + Send + 'static> Attributes for KeyEvent<E> {
+    fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
+        let filter = serde_json::to_string(&self.filter).map_err(|err| {
+            crate::error::InternalError::Serialize {
+                what: "key filter".into(),
+                err,
+            }
+        })?;
+        r.attribute(&format!("cabin-{}-key", self.event.name), filter);
+        self.event.render(r)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::html::Common;
+    use crate::html::h::button;
+    use crate::render::Renderer;
+
+    #[tokio::test]
+    async fn test_key_filter() {
+        assert_eq!(
+            button(())
+                .on_key_down(Key::Enter.with_ctrl(), ())
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<button cabin-keydown-key="{&quot;key&quot;:&quot;Enter&quot;,&quot;ctrl&quot;:true}" cabin-keydown="()" cabin-keydown-payload="null"></button>"#
+        );
+        assert_eq!(
+            serde_json::to_string(&KeyFilter::from(Key::Any).with_shift()).unwrap(),
+            r#"{"key":null,"shift":true}"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_key_filter_prevent_default() {
+        // The client prevents the default of keydowns with a specific key (also on inputs, e.g.
+        // to not submit the surrounding form on Enter), but not of those with `Key::Any` (which
+        // also keep propagating to document-wide shortcuts).
+        let html = crate::html::h::input()
+            .on_key_down(Key::Enter, ())
+            .render(Renderer::new(false, true))
+            .await
+            .unwrap()
+            .end()
+            .unwrap()
+            .html;
+        assert!(
+            html.contains(r#"cabin-keydown-key="{&quot;key&quot;:&quot;Enter&quot;}""#),
+            "{html}"
+        );
+        assert!(!html.contains("cabin-prevent-default"), "{html}");
+
+        let html = crate::view![
+            crate::html::h::div(shortcut(Key::Char('s').with_ctrl(), ())).on_key_down(Key::Any, ()),
+        ]
+        .render(Renderer::new(false, true))
+        .await
+        .unwrap()
+        .end()
+        .unwrap()
+        .html;
+        assert!(
+            html.starts_with(r#"<div cabin-keydown-key="{&quot;key&quot;:null}""#),
+            "{html}"
+        );
+        assert!(html.contains("<cabin-shortcut "), "{html}");
+        assert!(!html.contains("cabin-prevent-default"), "{html}");
+    }
+}
@@ -0,0 +1,388 @@
+//! Sanitize untrusted HTML (e.g. rich text from a CMS) by only keeping an allow-list of tags and
+//! attributes.
+//!
+//! ```ignore
+//! h::div(sanitize(&article.body))
+//! ```
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+use ammonia::UrlRelative;
+
+use crate::View;
+use crate::html::Raw;
+use crate::render::Renderer;
+use crate::view::RenderFuture;
+
+/// Sanitize the given `html` using the default [Policy].
+pub fn sanitize(html: &str) -> Sanitized {
+    Policy::default().sanitize(html)
+}
+
+/// HTML that went through a sanitizer [Policy] and is thus safe to be rendered unescaped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sanitized(Cow<'static, str>);
+
+/// Allow-list of tags and attributes kept when sanitizing HTML. Everything else is removed.
+#[derive(Debug, Clone)]
+pub struct Policy {
+    tags: HashSet<&'static str>,
+    tag_attributes: HashMap<&'static str, HashSet<&'static str>>,
+    generic_attributes: HashSet<&'static str>,
+    strip_comments: bool,
+    links: Links,
+    images: Images,
+}
+
+/// Policy for links (and other URLs like `cite`).
+#[derive(Debug, Clone)]
+pub struct Links {
+    schemes: HashSet<&'static str>,
+    allow_relative: bool,
+    rel: Option<Cow<'static, str>>,
+}
+
+/// Policy for images.
+#[derive(Debug, Clone)]
+pub struct Images {
+    allow: bool,
+    schemes: HashSet<&'static str>,
+    allow_relative: bool,
+}
+
+impl Policy {
+    /// A policy that keeps common formatting tags and attributes (e.g. `p`, `a`, `strong`, `ul`,
+    /// `img`, `table`), and drops everything else (like `script`, `style`, `iframe`, event
+    /// handler and `style` attributes).
+    pub fn new() -> Self {
+        let defaults = ammonia::Builder::default();
+        Self {
+            tags: defaults.clone_tags(),
+            tag_attributes: defaults.clone_tag_attributes(),
+            generic_attributes: defaults.clone_generic_attributes(),
+            strip_comments: true,
+            links: Links::default(),
+            images: Images::default(),
+        }
+    }
+
+    /// A policy that doesn't allow any tag or attribute (only keeps the text content).
+    pub fn empty() -> Self {
+        Self {
+            tags: HashSet::new(),
+            tag_attributes: HashMap::new(),
+            generic_attributes: HashSet::new(),
+            strip_comments: true,
+            links: Links::default(),
+            images: Images::deny(),
+        }
+    }
+
+    /// Allow the given tags.
+    pub fn allow_tags(mut self, tags: impl IntoIterator<Item = &'static str>) -> Self {
+        self.tags.extend(tags);
+        self
+    }
+
+    /// Remove the given tags (and all their attributes) from the allow-list.
+    pub fn deny_tags(mut self, tags: impl IntoIterator<Item = &'static str>) -> Self {
+        for tag in tags {
+            self.tags.remove(tag);
+            self.tag_attributes.remove(tag);
+        }
+        self
+    }
+
+    /// Allow the given attributes on the given tag.
+    pub fn allow_tag_attributes(
+        mut self,
+        tag: &'static str,
+        attributes: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        self.tag_attributes
+            .entry(tag)
+            .or_default()
+            .extend(attributes);
+        self
+    }
+
+    /// Allow the given attributes on all allowed tags.
+    pub fn allow_generic_attributes(
+        mut self,
+        attributes: impl IntoIterator<Item = &'static str>,
+    ) -> Self {
+        self.generic_attributes.extend(attributes);
+        self
+    }
+
+    /// Whether to remove HTML comments (default: `true`).
+    pub fn strip_comments(mut self, strip_comments: bool) -> Self {
+        self.strip_comments = strip_comments;
+        self
+    }
+
+    /// Set the policy for links.
+    pub fn links(mut self, links: Links) -> Self {
+        self.links = links;
+        self
+    }
+
+    /// Set the policy for images.
+    pub fn images(mut self, images: Images) -> Self {
+        self.images = images;
+        self
+    }
+
+    pub fn sanitize(&self, html: &str) -> Sanitized {
+        let mut tags = self.tags.clone();
+        if !self.images.allow {
+            tags.remove("img");
+        }
+
+        let mut tag_attributes = self.tag_attributes.clone();
+        if self.links.rel.is_some()
+            && let Some(a) = tag_attributes.get_mut("a")
+        {
+            // the `rel` attribute is set by the policy and must thus not be allowed
+            a.remove("rel");
+        }
+
+        let mut builder = ammonia::Builder::empty();
+        builder
+            .tags(tags)
+            .tag_attributes(tag_attributes)
+            .generic_attributes(self.generic_attributes.clone())
+            .url_schemes(
+                self.links
+                    .schemes
+                    .union(&self.images.schemes)
+                    .copied()
+                    .collect(),
+            )
+            .url_relative(if self.links.allow_relative || self.images.allow_relative {
+                UrlRelative::PassThrough
+            } else {
+                UrlRelative::Deny
+            })
+            .link_rel(self.links.rel.as_deref())
+            .strip_comments(self.strip_comments);
+
+        let links = self.links.clone();
+        let images = self.images.clone();
+        builder.attribute_filter(move |element, attribute, value| {
+            let allowed = match (element, attribute) {
+                ("img", "src") => images.allows(value),
+                (_, "href" | "src" | "cite") => links.allows(value),
+                _ => true,
+            };
+            allowed.then_some(Cow::Borrowed(value))
+        });
+
+        Sanitized(Cow::Owned(builder.clean(html).to_string()))
+    }
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Links {
+    /// Allow `http`, `https`, `mailto` and `tel` links as well as relative links, and set
+    /// `rel="noopener noreferrer"` on all links.
+    pub fn new() -> Self {
+        Self {
+            schemes: HashSet::from(["http", "https", "mailto", "tel"]),
+            allow_relative: true,
+            rel: Some(Cow::Borrowed("noopener noreferrer")),
+        }
+    }
+
+    /// The allowed URL schemes (replaces the default ones).
+    pub fn schemes(mut self, schemes: impl IntoIterator<Item = &'static str>) -> Self {
+        self.schemes = schemes.into_iter().collect();
+        self
+    }
+
+    /// Whether relative URLs are allowed.
+    pub fn allow_relative(mut self, allow_relative: bool) -> Self {
+        self.allow_relative = allow_relative;
+        self
+    }
+
+    /// The `rel` attribute set on all links (`None` to keep links without a `rel`).
+    pub fn rel(mut self, rel: impl Into<Option<Cow<'static, str>>>) -> Self {
+        self.rel = rel.into();
+        self
+    }
+
+    fn allows(&self, url: &str) -> bool {
+        match scheme(url) {
+            Some(scheme) => self.schemes.contains(scheme.as_str()),
+            None => self.allow_relative,
+        }
+    }
+}
+
+impl Default for Links {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Images {
+    /// Allow `http` and `https` images as well as relative image URLs.
+    pub fn new() -> Self {
+        Self {
+            allow: true,
+            schemes: HashSet::from(["http", "https"]),
+            allow_relative: true,
+        }
+    }
+
+    /// Remove all images.
+    pub fn deny() -> Self {
+        Self {
+            allow: false,
+            schemes: HashSet::new(),
+            allow_relative: false,
+        }
+    }
+
+    /// The allowed URL schemes (replaces the default ones). Add `data` to allow inline images.
+    pub fn schemes(mut self, schemes: impl IntoIterator<Item = &'static str>) -> Self {
+        self.schemes = schemes.into_iter().collect();
+        self
+    }
+
+    /// Whether relative URLs are allowed.
+    pub fn allow_relative(mut self, allow_relative: bool) -> Self {
+        self.allow_relative = allow_relative;
+        self
+    }
+
+    fn allows(&self, url: &str) -> bool {
+        if !self.allow {
+            return false;
+        }
+        match scheme(url) {
+            Some(scheme) => self.schemes.contains(scheme.as_str()),
+            None => self.allow_relative,
+        }
+    }
+}
+
+impl Default for Images {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sanitized {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl View for Sanitized {
+    fn render(self, r: Renderer) -> RenderFuture {
+        Raw(self.0).render(r)
+    }
+}
+
+impl From<Sanitized> for Raw {
+    fn from(sanitized: Sanitized) -> Self {
+        Raw(sanitized.0)
+    }
+}
+
+/// The lowercase scheme of the given URL, or `None` if it is a relative URL.
+fn scheme(url: &str) -> Option<String> {
+    let url = url.trim_start_matches(|c: char| c.is_ascii_whitespace() || c.is_ascii_control());
+    let end = url.find([':', '/', '?', '#'])?;
+    if !url[end..].starts_with(':') {
+        return None;
+    }
+    let scheme = &url[..end];
+    let mut chars = scheme.chars();
+    if !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        || !chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    {
+        return None;
+    }
+    Some(scheme.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remove_unsafe_content() {
+        assert_eq!(
+            sanitize(r#"<p onclick="alert(1)">Hi<script>alert(1)</script></p>"#).as_str(),
+            "<p>Hi</p>"
+        );
+        assert_eq!(
+            sanitize(r#"<a href="javascript:alert(1)">x</a>"#).as_str(),
+            r#"<a rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(
+            sanitize(r#"<a href="&#106;avascript:alert(1)">x</a>"#).as_str(),
+            r#"<a rel="noopener noreferrer">x</a>"#
+        );
+    }
+
+    #[test]
+    fn test_link_policy() {
+        assert_eq!(
+            sanitize(r#"<a href="https://example.com" rel="opener">x</a>"#).as_str(),
+            r#"<a href="https://example.com" rel="noopener noreferrer">x</a>"#
+        );
+        assert_eq!(
+            Policy::new()
+                .links(
+                    Links::new()
+                        .schemes(["https"])
+                        .allow_relative(false)
+                        .rel(None)
+                )
+                .sanitize(
+                    r#"<a href="/">a</a><a href="mailto:a@b.c">b</a><a href="https://c">c</a>"#
+                )
+                .as_str(),
+            r#"<a>a</a><a>b</a><a href="https://c">c</a>"#
+        );
+    }
+
+    #[test]
+    fn test_image_policy() {
+        let html = r#"<img src="/a.png"><img src="data:image/png;base64,AAAA">"#;
+        assert_eq!(sanitize(html).as_str(), r#"<img src="/a.png"><img>"#);
+        assert_eq!(
+            Policy::new()
+                .images(Images::new().schemes(["data"]).allow_relative(false))
+                .sanitize(html)
+                .as_str(),
+            r#"<img><img src="data:image/png;base64,AAAA">"#
+        );
+        assert_eq!(
+            Policy::new().images(Images::deny()).sanitize(html).as_str(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_scheme() {
+        assert_eq!(scheme("HTTPS://example.com").as_deref(), Some("https"));
+        assert_eq!(
+            scheme(" javascript:alert(1)").as_deref(),
+            Some("javascript")
+        );
+        assert_eq!(scheme("/foo:bar"), None);
+        assert_eq!(scheme("foo?a=b:c"), None);
+        assert_eq!(scheme("./a.png"), None);
+    }
+}
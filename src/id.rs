@@ -0,0 +1,161 @@
+//! Identifiers of events and boundaries. By default, they are the full Rust paths of the event
+//! types and boundary functions (e.g. `my_app::components::counter::Increment`). With the
+//! `short-ids` feature enabled, short hashes of these paths are used instead, which keeps the HTML
+//! small and doesn't expose the app's internal crate structure. The full paths are still
+//! available via [full_path] and [mapping_table] (e.g. for debugging).
+//!
+//! Wasm boundaries always use their full path, as it is used as the name of the wasm export.
+
+use std::{error, fmt};
+
+/// Length of compact ids.
+pub const COMPACT_LEN: usize = 11;
+
+/// All ids of events and boundaries (collected across all linked crates).
+#[cfg(not(target_arch = "wasm32"))]
+#[linkme::distributed_slice]
+pub static IDS: [Id];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Id {
+    pub kind: IdKind,
+    /// The id as used in HTML attributes and URLs.
+    pub id: &'static str,
+    /// The full Rust path of the event type or boundary function.
+    pub full_path: &'static str,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdKind {
+    Event,
+    Boundary,
+}
+
+/// The compact id of the `full_path` (a base62 encoded 64 bit FNV-1a hash).
+pub const fn compact(full_path: &str) -> [u8; COMPACT_LEN] {
+    const ALPHABET: &[u8; 62] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let bytes = full_path.as_bytes();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+
+    let mut id = [b'0'; COMPACT_LEN];
+    let mut i = COMPACT_LEN;
+    while i > 0 {
+        i -= 1;
+        id[i] = ALPHABET[(hash % 62) as usize];
+        hash /= 62;
+    }
+    id
+}
+
+/// Select either the `full_path` or its `compact` id, depending on whether the `short-ids`
+/// feature is enabled. Used by `derive(Event)` and `#[cabin::boundary]`.
+pub const fn select(full_path: &'static str, compact: &'static [u8; COMPACT_LEN]) -> &'static str {
+    if cfg!(feature = "short-ids") {
+        match std::str::from_utf8(compact) {
+            Ok(id) => id,
+            Err(_) => panic!("compact id must be valid utf8"),
+        }
+    } else {
+        full_path
+    }
+}
+
+/// The full Rust path of the event or boundary of the given `id`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn full_path(kind: IdKind, id: &str) -> Option<&'static str> {
+    IDS.iter()
+        .find(|entry| entry.kind == kind && entry.id == id)
+        .map(|entry| entry.full_path)
+}
+
+/// The mapping of all ids to the full paths of their events and boundaries as a plain text
+/// table (one `kind id full_path` line each), e.g. to look up short ids while debugging. Served at
+/// `/__cabin/ids` by `cabin_service::boundaries` in debug builds.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn mapping_table() -> String {
+    let mut ids = IDS.iter().collect::<Vec<_>>();
+    ids.sort_by_key(|entry| (entry.kind == IdKind::Boundary, entry.full_path));
+    ids.dedup();
+    let width = ids.iter().map(|entry| entry.id.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    for entry in ids {
+        let kind = match entry.kind {
+            IdKind::Event => "event",
+            IdKind::Boundary => "boundary",
+        };
+        table += &format!("{kind:<8} {:<width$} {}\n", entry.id, entry.full_path);
+    }
+    table
+}
+
+/// Check that no two events (or boundaries) share the same id, e.g. due to a hash collision of
+/// their compact ids.
+///
+/// Ids of all linked crates are checked. `cabin::BOUNDARIES!()` generates a test (named
+/// `__boundaries::unique_ids`) that runs this check as part of `cargo test` of the crate calling
+/// it, which catches collisions in CI before they are deployed. Collisions are also detected when
+/// the boundaries are registered on startup.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn check_unique() -> Result<(), IdCollision> {
+    let mut seen = std::collections::HashMap::with_capacity(IDS.len());
+    for entry in IDS.iter() {
+        if let Some(other) = seen.insert((entry.kind, entry.id), entry.full_path)
+            && other != entry.full_path
+        {
+            return Err(IdCollision {
+                id: entry.id,
+                full_paths: [other, entry.full_path],
+            });
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct IdCollision {
+    pub id: &'static str,
+    pub full_paths: [&'static str; 2],
+}
+
+impl error::Error for IdCollision {}
+
+impl fmt::Display for IdCollision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` and `{}` share the same id `{}`",
+            self.full_paths[0], self.full_paths[1], self.id
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: [u8; COMPACT_LEN] = compact("my_app::counter::Increment");
+
+    #[test]
+    fn test_compact_id() {
+        let id = compact("my_app::counter::Increment");
+        assert_eq!(id, compact("my_app::counter::Increment"));
+        assert_ne!(id, compact("my_app::counter::Decrement"));
+        assert!(id.iter().all(u8::is_ascii_alphanumeric));
+        assert_eq!(
+            select("my_app::counter::Increment", &ID),
+            if cfg!(feature = "short-ids") {
+                std::str::from_utf8(&ID).unwrap()
+            } else {
+                "my_app::counter::Increment"
+            }
+        );
+        check_unique().unwrap();
+    }
+}
@@ -2,7 +2,9 @@
 
 extern crate self as cabin;
 
-pub use cabin_macros::{Attribute, BOUNDARIES, Event, boundary, view_macro};
+pub use cabin_macros::{Attribute, BOUNDARIES, Event, Pack, boundary, view_macro};
+#[cfg(not(target_arch = "wasm32"))]
+pub use document::{Document, document};
 pub use error::Error;
 pub use html::h;
 pub use http::StatusCode;
@@ -15,10 +17,15 @@ pub use view::View;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod boundary_registry;
+pub mod command;
+pub mod csp;
+#[cfg(not(target_arch = "wasm32"))]
+mod document;
 pub mod error;
 pub mod event;
 pub mod fire_event;
 pub mod html;
+pub mod id;
 pub mod multipart;
 pub mod pack;
 mod pair;
@@ -30,7 +37,10 @@ pub mod scope;
 pub mod serde;
 #[cfg(not(target_arch = "wasm32"))]
 mod server;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod state_store;
 pub mod style;
+pub mod svg;
 pub mod view;
 #[cfg(target_arch = "wasm32")]
 mod wasm_exports;
@@ -1,5 +1,14 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+pub use cabin_macros::Pack;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tokio::sync::Notify;
+use tokio::sync::futures::OwnedNotified;
 
 pub enum Bound<T: Pack<C>, C> {
     Packed(T::Packed),
@@ -13,6 +22,31 @@ impl<T: Pack<C>, C> Bound<T, C> {
             Bound::Unpacked(unpacked) => Ok(unpacked),
         }
     }
+
+    /// Unpack the value. Values unpacked concurrently during the same request are unpacked
+    /// together via a single [Pack::unpack_many] call. Unpacked values are cached for the rest of
+    /// the request.
+    ///
+    /// Only views that are rendered concurrently are batched. Items of a list are rendered one
+    /// after another by default, so render them via
+    /// [render_concurrently](crate::view::IteratorExt::render_concurrently) to unpack their values
+    /// with a single query.
+    ///
+    /// Batches and caches are kept per request and connection type `C`, i.e. all connections of
+    /// the same type used during a request are expected to access the same data (e.g. the same
+    /// database). The batch is unpacked with the connection of the caller that unpacks it.
+    pub async fn unpack_batched(self, conn: &C) -> Result<T, T::Error>
+    where
+        T: Clone + Send + 'static,
+        T::Packed: Hash + Eq + Clone + Send,
+        T::Error: Send,
+        C: 'static,
+    {
+        match self {
+            Bound::Packed(packed) => load(packed, conn).await,
+            Bound::Unpacked(unpacked) => Ok(unpacked),
+        }
+    }
 }
 
 pub trait Pack<C>: Sized {
@@ -22,6 +56,21 @@ pub trait Pack<C>: Sized {
     fn pack(&self) -> Self::Packed;
     fn unpack(pack: Self::Packed, conn: &C) -> impl Future<Output = Result<Self, Self::Error>>;
 
+    /// Unpack multiple values at once (e.g. with a single database query). Must return one result
+    /// per packed value, in the same order. Defaults to unpacking them one by one.
+    fn unpack_many(
+        packs: Vec<Self::Packed>,
+        conn: &C,
+    ) -> impl Future<Output = Vec<Result<Self, Self::Error>>> {
+        async move {
+            let mut results = Vec::with_capacity(packs.len());
+            for pack in packs {
+                results.push(Self::unpack(pack, conn).await);
+            }
+            results
+        }
+    }
+
     fn packed(&self) -> Bound<Self, C> {
         Bound::Packed(self.pack())
     }
@@ -31,6 +80,158 @@ pub trait Pack<C>: Sized {
     }
 }
 
+/// Request-scoped batching loader of the values of type `T`.
+struct Loader<T: Pack<C>, C> {
+    /// Successfully unpacked values.
+    cache: HashMap<T::Packed, T>,
+    /// Errors waiting to be picked up by the caller of the corresponding value.
+    errors: HashMap<T::Packed, T::Error>,
+    /// Values of the next batch.
+    pending: Vec<T::Packed>,
+    /// Values of the batch currently being unpacked.
+    in_flight: HashSet<T::Packed>,
+    /// Notified once the batch currently being unpacked is done.
+    done: Arc<Notify>,
+    marker: PhantomData<fn(&C)>,
+}
+
+impl<T: Pack<C>, C> Default for Loader<T, C> {
+    fn default() -> Self {
+        Self {
+            cache: Default::default(),
+            errors: Default::default(),
+            pending: Default::default(),
+            in_flight: Default::default(),
+            done: Default::default(),
+            marker: PhantomData,
+        }
+    }
+}
+
+enum Next<T: Pack<C>, C> {
+    Done(Result<T, T::Error>),
+    Unpack(Vec<T::Packed>),
+    Wait(Pin<Box<OwnedNotified>>),
+    Fallback,
+}
+
+async fn load<T, C>(packed: T::Packed, conn: &C) -> Result<T, T::Error>
+where
+    T: Pack<C> + Clone + Send + 'static,
+    T::Packed: Hash + Eq + Clone + Send,
+    T::Error: Send,
+    C: 'static,
+{
+    let cached = with_loader(|loader: &mut Loader<T, C>| {
+        if let Some(value) = loader.cache.get(&packed) {
+            return Some(value.clone());
+        }
+        if !loader.pending.contains(&packed) && !loader.in_flight.contains(&packed) {
+            loader.pending.push(packed.clone());
+        }
+        None
+    });
+    match cached {
+        // not inside of a request
+        None => return T::unpack(packed, conn).await,
+        Some(Some(value)) => return Ok(value),
+        Some(None) => {}
+    }
+
+    // Give other views being rendered concurrently the chance to add their values to the batch.
+    tokio::task::yield_now().await;
+
+    loop {
+        let next = with_loader(|loader: &mut Loader<T, C>| {
+            if let Some(value) = loader.cache.get(&packed) {
+                Next::Done(Ok(value.clone()))
+            } else if let Some(err) = loader.errors.remove(&packed) {
+                Next::Done(Err(err))
+            } else if loader.pending.contains(&packed) {
+                let batch = std::mem::take(&mut loader.pending);
+                loader.in_flight.extend(batch.iter().cloned());
+                Next::Unpack(batch)
+            } else if loader.in_flight.contains(&packed) {
+                // Register for the notification while still holding the lock, so that it cannot
+                // be missed if the batch finishes before we start waiting.
+                let mut done = Box::pin(loader.done.clone().notified_owned());
+                done.as_mut().enable();
+                Next::Wait(done)
+            } else {
+                // e.g. the error was already picked up by another caller of the same value
+                Next::Fallback
+            }
+        })
+        .unwrap_or(Next::Fallback);
+
+        match next {
+            Next::Done(result) => return result,
+            Next::Unpack(batch) => {
+                let guard = InFlight::<T, C> {
+                    batch: batch.clone(),
+                    marker: PhantomData,
+                };
+                let results = T::unpack_many(batch, conn).await;
+                with_loader(|loader: &mut Loader<T, C>| {
+                    for (packed, result) in guard.batch.iter().zip(results) {
+                        match result {
+                            Ok(value) => {
+                                loader.cache.insert(packed.clone(), value);
+                            }
+                            Err(err) => {
+                                loader.errors.insert(packed.clone(), err);
+                            }
+                        }
+                    }
+                });
+                drop(guard);
+            }
+            Next::Wait(done) => done.await,
+            Next::Fallback => return T::unpack(packed, conn).await,
+        }
+    }
+}
+
+fn with_loader<T, C, R>(f: impl FnOnce(&mut Loader<T, C>) -> R) -> Option<R>
+where
+    T: Pack<C> + Send + 'static,
+    T::Packed: Hash + Eq + Send,
+    T::Error: Send,
+    C: 'static,
+{
+    crate::scope::with_local(f)
+}
+
+/// Marks the batch as done once the batch was unpacked (or the future unpacking it was dropped),
+/// and wakes up the callers waiting for it.
+struct InFlight<T, C>
+where
+    T: Pack<C> + Send + 'static,
+    T::Packed: Hash + Eq + Send,
+    T::Error: Send,
+    C: 'static,
+{
+    batch: Vec<T::Packed>,
+    marker: PhantomData<fn(&C)>,
+}
+
+impl<T, C> Drop for InFlight<T, C>
+where
+    T: Pack<C> + Send + 'static,
+    T::Packed: Hash + Eq + Send,
+    T::Error: Send,
+    C: 'static,
+{
+    fn drop(&mut self) {
+        with_loader(|loader: &mut Loader<T, C>| {
+            for packed in &self.batch {
+                loader.in_flight.remove(packed);
+            }
+            loader.done.notify_waiters();
+        });
+    }
+}
+
 impl<T: Pack<C>, C> Serialize for Bound<T, C> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -78,3 +279,83 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::scope::Scope;
+
+    #[derive(Default)]
+    struct Db {
+        batches: Mutex<Vec<Vec<u32>>>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Pack)]
+    #[pack(conn = Db, error = String, unpack = Row::find, unpack_many = Row::find_many)]
+    struct Row {
+        id: u32,
+    }
+
+    impl Row {
+        async fn find(id: u32, db: &Db) -> Result<Self, String> {
+            Self::find_many(vec![id], db).await.remove(0)
+        }
+
+        async fn find_many(ids: Vec<u32>, db: &Db) -> Vec<Result<Self, String>> {
+            db.batches.lock().unwrap().push(ids.clone());
+            ids.into_iter()
+                .map(|id| {
+                    if id == 0 {
+                        Err("not found".to_string())
+                    } else {
+                        Ok(Row { id })
+                    }
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batch_unpack() {
+        let db = Db::default();
+        let other_db = Db::default();
+        let rows = Scope::new(false, false)
+            .run(async {
+                let rows = futures_util::future::join_all(
+                    [1, 2, 1, 0].map(|id| Bound::<Row, Db>::Packed(id).unpack_batched(&db)),
+                )
+                .await;
+                // cached for the rest of the request (also for other connections of that type)
+                let cached = Bound::<Row, Db>::Packed(2).unpack_batched(&db).await;
+                Bound::<Row, Db>::Packed(1)
+                    .unpack_batched(&other_db)
+                    .await
+                    .unwrap();
+                // and batched with any connection of that type
+                let rows2 = futures_util::future::join(
+                    Bound::<Row, Db>::Packed(3).unpack_batched(&db),
+                    Bound::<Row, Db>::Packed(4).unpack_batched(&other_db),
+                )
+                .await;
+                assert_eq!(rows2, (Ok(Row { id: 3 }), Ok(Row { id: 4 })));
+                Ok((rows, cached))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            rows.0,
+            [
+                Ok(Row { id: 1 }),
+                Ok(Row { id: 2 }),
+                Ok(Row { id: 1 }),
+                Err("not found".to_string())
+            ]
+        );
+        assert_eq!(rows.1, Ok(Row { id: 2 }));
+        assert_eq!(*db.batches.lock().unwrap(), [vec![1, 2, 0], vec![3, 4]]);
+        assert!(other_db.batches.lock().unwrap().is_empty());
+    }
+}
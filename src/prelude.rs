@@ -44,4 +44,19 @@ pub use crate::html::elements::time::Time as _;
 pub use crate::html::h;
 pub use crate::pack::Pack as _;
 pub use crate::style::{Style as _, StyleExt as _, SubStyle as _, ThemeExt as _, ThemeSubExt as _};
+pub use crate::svg::elements::circle::Circle as _;
+pub use crate::svg::elements::ellipse::Ellipse as _;
+pub use crate::svg::elements::line::Line as _;
+pub use crate::svg::elements::linear_gradient::LinearGradient as _;
+pub use crate::svg::elements::path::Path as _;
+pub use crate::svg::elements::polygon::Polygon as _;
+pub use crate::svg::elements::polyline::Polyline as _;
+pub use crate::svg::elements::presentation::Presentation as _;
+pub use crate::svg::elements::radial_gradient::RadialGradient as _;
+pub use crate::svg::elements::rect::Rect as _;
+pub use crate::svg::elements::stop::Stop as _;
+pub use crate::svg::elements::svg::Svg as _;
+pub use crate::svg::elements::symbol::Symbol as _;
+pub use crate::svg::elements::text::Text as _;
+pub use crate::svg::elements::use_::Use as _;
 pub use crate::view::view;
@@ -20,11 +20,80 @@ pub struct Renderer {
     out: String,
     headers: HeaderMap<HeaderValue>,
     styles: HashMap<ClassName, StyleDefinition>,
-    hasher: XxHash32,
+    hasher: RenderHasher,
     is_update: bool,
     disable_hashes: bool,
 }
 
+/// The hasher of a [Renderer]. Renderers of views rendered concurrently record the bytes hashed at
+/// their top level instead, so that appending them results in the same hash as if the views were
+/// rendered one after another into the same renderer.
+enum RenderHasher {
+    Hash(XxHash32),
+    Record(Vec<u8>),
+}
+
+impl Default for RenderHasher {
+    fn default() -> Self {
+        Self::Hash(XxHash32::default())
+    }
+}
+
+impl Hasher for RenderHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        match self {
+            Self::Hash(hasher) => hasher.write(bytes),
+            Self::Record(recorded) => recorded.extend_from_slice(bytes),
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        match self {
+            Self::Hash(hasher) => hasher.finish(),
+            Self::Record(recorded) => {
+                let mut hasher = XxHash32::default();
+                hasher.write(recorded);
+                hasher.finish()
+            }
+        }
+    }
+}
+
+/// Like [HeaderMap::extend], except that client commands and fired events are accumulated
+/// instead of replaced.
+fn merge_headers(into: &mut HeaderMap<HeaderValue>, from: &mut HeaderMap<HeaderValue>) {
+    let mut name = None;
+    for (next_name, value) in from.drain() {
+        if let Some(next_name) = next_name {
+            if next_name != crate::command::HEADER_NAME
+                && next_name != crate::fire_event::HEADER_NAME
+            {
+                into.remove(&next_name);
+            }
+            name = Some(next_name);
+        }
+        if let Some(name) = &name {
+            into.append(name, value);
+        }
+    }
+}
+
+/// Create a header value from JSON. Header values must be visible ASCII, so all other characters
+/// are escaped (JSON already escapes control characters).
+pub(crate) fn json_header_value(json: &str) -> Result<HeaderValue, crate::Error> {
+    let mut ascii = String::with_capacity(json.len());
+    for c in json.chars() {
+        if c.is_ascii() {
+            ascii.push(c);
+        } else {
+            for unit in c.encode_utf16(&mut [0; 2]) {
+                write!(ascii, "\\u{unit:04x}").unwrap();
+            }
+        }
+    }
+    Ok(HeaderValue::from_str(&ascii).map_err(InternalError::InvalidHeaderValue)?)
+}
+
 pub struct Out {
     pub html: String,
     pub headers: HeaderMap<HeaderValue>,
@@ -39,7 +108,7 @@ impl Renderer {
             // 15%. Thus decided to keep them empty by default and not reserve any
             // capacity.
             styles: Default::default(),
-            hasher: XxHash32::default(),
+            hasher: Default::default(),
             disable_hashes,
             is_update,
         }
@@ -49,7 +118,14 @@ impl Renderer {
         self.out.truncate(0);
         self.headers.clear();
         self.styles.clear();
-        self.hasher = XxHash32::default();
+        self.hasher = Default::default();
+    }
+
+    /// Record the bytes hashed at the top level of this renderer instead of hashing them, see
+    /// [RenderHasher].
+    pub(crate) fn record_hash(mut self) -> Self {
+        self.hasher = RenderHasher::Record(Vec::new());
+        self
     }
 
     pub fn append(&mut self, mut other: Renderer) {
@@ -63,11 +139,10 @@ impl Renderer {
         }
         if self.headers.is_empty() {
             std::mem::swap(&mut self.headers, &mut other.headers);
-        } else if other.headers.capacity() > self.headers.capacity() {
-            other.headers.extend(self.headers.drain());
-            std::mem::swap(&mut self.headers, &mut other.headers);
         } else {
-            self.headers.extend(other.headers.drain());
+            // Always merge into `self` to keep the document order of repeated headers (e.g.
+            // client commands)
+            merge_headers(&mut self.headers, &mut other.headers);
         }
         if self.styles.is_empty() {
             std::mem::swap(&mut self.styles, &mut other.styles);
@@ -77,8 +152,10 @@ impl Renderer {
         } else {
             self.styles.extend(other.styles.drain());
         }
-        let hash = other.hasher.finish() as u32;
-        self.hasher.write_u32(hash);
+        match &other.hasher {
+            RenderHasher::Hash(hasher) => self.hasher.write_u32(hasher.finish() as u32),
+            RenderHasher::Record(recorded) => self.hasher.write(recorded),
+        }
         Scope::release_renderer_to_task(other);
     }
 
@@ -184,7 +261,7 @@ impl Renderer {
 pub struct ElementRenderer {
     tag: &'static str,
     pub(crate) renderer: Renderer,
-    parent_hasher: XxHash32,
+    parent_hasher: RenderHasher,
     content_started: bool,
     hash_offset: Option<usize>,
 }
@@ -206,6 +283,31 @@ impl ElementRenderer {
         write!(&mut self.renderer, r#"""#).unwrap();
     }
 
+    /// Write an attribute that is not considered for the element's hash. Used for values that
+    /// change with every request (like a CSP nonce) and would otherwise force every update to
+    /// re-patch the element and all its ascendants.
+    pub(crate) fn unhashed_attribute(
+        &mut self,
+        name: &str,
+        value: impl Display,
+    ) -> Result<(), crate::Error> {
+        if self.content_started {
+            return Err(InternalError::AttributeAfterContent {
+                name: name.to_string(),
+            }
+            .into());
+        }
+
+        write!(&mut self.renderer, r#" {name}=""#).unwrap();
+        Write::write_fmt(
+            &mut Escape::attribute_value(&mut self.renderer),
+            format_args!("{value}"),
+        )
+        .unwrap();
+        write!(&mut self.renderer, r#"""#).unwrap();
+        Ok(())
+    }
+
     pub fn empty_attribute(&mut self, name: &str) {
         if self.content_started {
             todo!("throw error: content started");
@@ -1,23 +1,39 @@
-use std::any::Any;
-use std::cell::RefCell;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::error;
 use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, OnceLock};
 
 use multer::Multipart;
 use serde::de::DeserializeOwned;
 use serde_json::value::RawValue;
+use tokio::sync::Notify;
+use tokio::sync::futures::OwnedNotified;
+use tracing::Instrument;
 
+use crate::csp::Nonce;
 use crate::error::InternalError;
 use crate::render::Renderer;
 
 tokio::task_local! {
-    static SCOPE: Scope;
+    static SCOPE: Arc<Scope>;
 }
 
 pub struct Scope {
-    event: RefCell<Option<Event>>,
-    multipart: RefCell<Option<Multipart<'static>>>,
-    error: RefCell<Option<InternalError>>,
-    renderer_pool: RefCell<Vec<Renderer>>,
+    event: Mutex<Option<Event>>,
+    multipart: Mutex<Option<Multipart<'static>>>,
+    error: Mutex<Option<InternalError>>,
+    renderer_pool: Mutex<Vec<Renderer>>,
+    /// Results of [memo] calls, by key and result type.
+    memos: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+    after_response: AfterResponse,
+    #[cfg(feature = "server-timing")]
+    timings: Timings,
+    /// Request-scoped state (e.g. the batching loaders of [crate::pack]), by type.
+    locals: Mutex<HashMap<TypeId, Box<dyn Any + Send>>>,
+    nonce: Option<Nonce>,
     is_update: bool,
     disable_hashes: bool,
 }
@@ -39,7 +55,7 @@ where
 {
     SCOPE
         .try_with(|scope| {
-            let mut event = scope.event.borrow_mut();
+            let mut event = scope.event.lock().unwrap();
             let event = event.as_mut()?;
             match event {
                 Event::Raw { id, payload } => {
@@ -48,6 +64,8 @@ where
                         return None;
                     }
 
+                    let _span =
+                        tracing::debug_span!("deserialize_event", event_id = E::ID).entered();
                     match payload {
                         Payload::Json(payload) => match serde_json::from_str(payload.get()) {
                             Ok(payload) => {
@@ -56,7 +74,7 @@ where
                             }
                             Err(err) => {
                                 tracing::debug!(?payload, "event payload");
-                                (*scope.error.borrow_mut()) = Some(InternalError::Deserialize {
+                                (*scope.error.lock().unwrap()) = Some(InternalError::Deserialize {
                                     what: "event json payload",
                                     err: Box::new(err),
                                 });
@@ -72,7 +90,7 @@ where
                                 }
                                 Err(err) => {
                                     tracing::debug!(payload, "event payload");
-                                    (*scope.error.borrow_mut()) =
+                                    (*scope.error.lock().unwrap()) =
                                         Some(InternalError::Deserialize {
                                             what: "event urlencoded payload",
                                             err: Box::new(err),
@@ -91,7 +109,7 @@ where
                             }
                             Err(err) => {
                                 tracing::debug!(payload, "event payload");
-                                (*scope.error.borrow_mut()) = Some(InternalError::Deserialize {
+                                (*scope.error.lock().unwrap()) = Some(InternalError::Deserialize {
                                     what: "event empty urlencoded payload",
                                     err: Box::new(err),
                                 });
@@ -113,7 +131,7 @@ where
 {
     SCOPE
         .try_with(|scope| {
-            let mut event = scope.event.borrow_mut();
+            let mut event = scope.event.lock().unwrap();
             match event.take()? {
                 Event::Raw { id, payload } => {
                     let event_id = E::ID;
@@ -122,12 +140,14 @@ where
                         return None;
                     }
 
+                    let _span =
+                        tracing::debug_span!("deserialize_event", event_id = E::ID).entered();
                     match payload {
                         Payload::Json(payload) => match serde_json::from_str(payload.get()) {
                             Ok(payload) => Some(payload),
                             Err(err) => {
                                 tracing::debug!(?payload, "event payload");
-                                (*scope.error.borrow_mut()) = Some(InternalError::Deserialize {
+                                (*scope.error.lock().unwrap()) = Some(InternalError::Deserialize {
                                     what: "event json payload",
                                     err: Box::new(err),
                                 });
@@ -140,7 +160,7 @@ where
                                 Ok(payload) => Some(payload),
                                 Err(err) => {
                                     tracing::debug!(payload, "event payload");
-                                    (*scope.error.borrow_mut()) =
+                                    (*scope.error.lock().unwrap()) =
                                         Some(InternalError::Deserialize {
                                             what: "event urlencoded payload",
                                             err: Box::new(err),
@@ -156,7 +176,7 @@ where
                             Ok(payload) => Some(payload),
                             Err(err) => {
                                 tracing::debug!(payload, "event payload");
-                                (*scope.error.borrow_mut()) = Some(InternalError::Deserialize {
+                                (*scope.error.lock().unwrap()) = Some(InternalError::Deserialize {
                                     what: "event empty urlencoded payload",
                                     err: Box::new(err),
                                 });
@@ -180,7 +200,287 @@ where
 
 pub fn take_multipart() -> Option<Multipart<'static>> {
     SCOPE
-        .try_with(|scope| scope.multipart.borrow_mut().take())
+        .try_with(|scope| scope.multipart.lock().unwrap().take())
+        .ok()
+        .flatten()
+}
+
+/// Spawn a new task that has access to the scope of the current request (e.g. to [event],
+/// [take_event] and [take_multipart]), unlike tasks spawned via [tokio::spawn]. Useful to
+/// render expensive views on another thread.
+pub fn spawn<F>(future: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match SCOPE.try_with(Arc::clone) {
+        Ok(scope) => tokio::spawn(SCOPE.scope(scope, future)),
+        Err(_) => tokio::spawn(future),
+    }
+}
+
+/// Like [spawn], but runs the blocking function `f` on a thread for blocking operations (see
+/// [tokio::task::spawn_blocking]).
+pub fn spawn_blocking<F, R>(f: F) -> tokio::task::JoinHandle<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    match SCOPE.try_with(Arc::clone) {
+        Ok(scope) => tokio::task::spawn_blocking(move || SCOPE.sync_scope(scope, f)),
+        Err(_) => tokio::task::spawn_blocking(f),
+    }
+}
+
+type Hook = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+type ErrorHandler = dyn Fn(Box<dyn error::Error + Send + Sync>) + Send + Sync;
+
+static ERROR_HANDLER: OnceLock<Box<ErrorHandler>> = OnceLock::new();
+
+/// Run the `future` once the response of the current request was produced, without delaying it
+/// (e.g. to send an email or write an audit log entry). The hooks of a request run one after
+/// another in a background task, in the tracing span they were registered in. They are discarded
+/// if the request fails. Errors are passed to the error handler (see
+/// [set_after_response_error_handler]).
+pub fn after_response<F, E>(future: F)
+where
+    F: Future<Output = Result<(), E>> + Send + 'static,
+    E: Into<Box<dyn error::Error + Send + Sync>>,
+{
+    let hook = async move {
+        if let Err(err) = future.await {
+            match ERROR_HANDLER.get() {
+                Some(handler) => handler(err.into()),
+                None => {
+                    let err = err.into();
+                    tracing::error!(%err, "after response hook failed");
+                }
+            }
+        }
+    }
+    .instrument(tracing::Span::current());
+
+    match SCOPE.try_with(|scope| scope.after_response.clone()) {
+        Ok(hooks) => hooks.0.lock().unwrap().push(Box::pin(hook)),
+        // not inside of a request
+        Err(_) => {
+            tokio::spawn(hook);
+        }
+    }
+}
+
+/// Set the handler for errors of [after_response] hooks (logs them as errors by default). Returns
+/// `false` if a handler was already set.
+pub fn set_after_response_error_handler(
+    handler: impl Fn(Box<dyn error::Error + Send + Sync>) + Send + Sync + 'static,
+) -> bool {
+    ERROR_HANDLER.set(Box::new(handler)).is_ok()
+}
+
+/// The [after_response] hooks of a request.
+#[derive(Default, Clone)]
+pub(crate) struct AfterResponse(Arc<Mutex<Vec<Hook>>>);
+
+impl AfterResponse {
+    /// Run the hooks in a background task.
+    pub(crate) fn spawn(self) {
+        let hooks = std::mem::take(&mut *self.0.lock().unwrap());
+        if hooks.is_empty() {
+            return;
+        }
+        tokio::spawn(async move {
+            for hook in hooks {
+                hook.await;
+            }
+        });
+    }
+}
+
+/// Build the styles via `f`, measuring how long it takes (see [Timings]).
+pub(crate) fn build_styles<T>(f: impl FnOnce() -> T) -> T {
+    let _span = tracing::debug_span!("build_styles").entered();
+    #[cfg(feature = "server-timing")]
+    let start = std::time::Instant::now();
+    let result = f();
+    #[cfg(feature = "server-timing")]
+    SCOPE
+        .try_with(|scope| *scope.timings.styles.lock().unwrap() += start.elapsed())
+        .ok();
+    result
+}
+
+/// Durations of the render phases of a request, sent to the client via the `Server-Timing`
+/// header (if the `server-timing` feature is enabled).
+#[cfg(feature = "server-timing")]
+#[derive(Clone)]
+pub(crate) struct Timings {
+    started_at: std::time::Instant,
+    styles: Arc<Mutex<std::time::Duration>>,
+}
+
+#[cfg(feature = "server-timing")]
+impl Timings {
+    fn new() -> Self {
+        Self {
+            started_at: std::time::Instant::now(),
+            styles: Default::default(),
+        }
+    }
+
+    /// Add the `Server-Timing` header of the render (until now), the style build and the response
+    /// size.
+    pub(crate) fn insert_header(&self, headers: &mut http::HeaderMap, size: usize) {
+        let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+        let value = format!(
+            "render;dur={:.3}, styles;dur={:.3}, size;desc=\"{size} bytes\"",
+            ms(self.started_at.elapsed()),
+            ms(*self.styles.lock().unwrap()),
+        );
+        if let Ok(value) = http::HeaderValue::from_str(&value) {
+            headers.insert(http::HeaderName::from_static("server-timing"), value);
+        }
+    }
+}
+
+/// The CSP nonce of the current request (see [crate::csp::with_nonce]). Already applied to all
+/// scripts and styles emitted by cabin, use it for any additional inline scripts or styles.
+pub fn nonce() -> Option<Nonce> {
+    SCOPE.try_with(|scope| scope.nonce.clone()).ok().flatten()
+}
+
+type Memos<K, T> = HashMap<K, Memo<T>>;
+
+enum Memo<T> {
+    Running(Arc<Notify>),
+    Ready(T),
+}
+
+enum MemoNext<T> {
+    Ready(T),
+    Wait(Pin<Box<OwnedNotified>>),
+    Run(Arc<Notify>),
+}
+
+/// Run the future returned by `f` once per request for the given `key`, and share its result with
+/// all other calls with the same `key` (and result type) during the request, including calls of
+/// views rendered concurrently. Useful for data needed by multiple views, e.g.:
+///
+/// ```rust,ignore
+/// let user = cabin::scope::memo("current_user", || load_current_user(&db)).await;
+/// ```
+///
+/// Must not be called recursively with the same `key`, as it would wait for itself.
+pub async fn memo<K, T, F, Fut>(key: K, f: F) -> T
+where
+    K: Hash + Eq + Clone + Send + 'static,
+    T: Clone + Send + 'static,
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = T>,
+{
+    loop {
+        let next = SCOPE.try_with(|scope| {
+            let mut memos = scope.memos.lock().unwrap();
+            let memos = memos
+                .entry(TypeId::of::<Memos<K, T>>())
+                .or_insert_with(|| Box::new(Memos::<K, T>::new()))
+                .downcast_mut::<Memos<K, T>>()
+                .expect("memos are stored by their type id");
+            match memos.get(&key) {
+                Some(Memo::Ready(value)) => MemoNext::Ready(value.clone()),
+                Some(Memo::Running(done)) => {
+                    // Register for the notification while still holding the lock, so that it
+                    // cannot be missed if the memo completes before we start waiting.
+                    let mut done = Box::pin(done.clone().notified_owned());
+                    done.as_mut().enable();
+                    MemoNext::Wait(done)
+                }
+                None => {
+                    let done = Arc::new(Notify::new());
+                    memos.insert(key.clone(), Memo::Running(done.clone()));
+                    MemoNext::Run(done)
+                }
+            }
+        });
+
+        match next {
+            // not inside of a request
+            Err(_) => return f().await,
+            Ok(MemoNext::Ready(value)) => return value,
+            Ok(MemoNext::Wait(done)) => done.await,
+            Ok(MemoNext::Run(done)) => {
+                let guard = MemoGuard::<K, T> {
+                    key: key.clone(),
+                    done,
+                    marker: std::marker::PhantomData,
+                };
+                let value = f().await;
+                SCOPE
+                    .try_with(|scope| {
+                        let mut memos = scope.memos.lock().unwrap();
+                        if let Some(memos) = memos
+                            .get_mut(&TypeId::of::<Memos<K, T>>())
+                            .and_then(|memos| memos.downcast_mut::<Memos<K, T>>())
+                        {
+                            memos.insert(key, Memo::Ready(value.clone()));
+                        }
+                    })
+                    .ok();
+                drop(guard);
+                return value;
+            }
+        }
+    }
+}
+
+/// Wakes up the calls waiting for the result of a [memo] once it is ready. If the future was
+/// dropped before it completed, the memo is reset so that the next call runs it again.
+struct MemoGuard<K, T>
+where
+    K: Hash + Eq + Send + 'static,
+    T: Send + 'static,
+{
+    key: K,
+    done: Arc<Notify>,
+    marker: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<K, T> Drop for MemoGuard<K, T>
+where
+    K: Hash + Eq + Send + 'static,
+    T: Send + 'static,
+{
+    fn drop(&mut self) {
+        SCOPE
+            .try_with(|scope| {
+                let mut memos = scope.memos.lock().unwrap();
+                if let Some(memos) = memos
+                    .get_mut(&TypeId::of::<Memos<K, T>>())
+                    .and_then(|memos| memos.downcast_mut::<Memos<K, T>>())
+                    && matches!(memos.get(&self.key), Some(Memo::Running(_)))
+                {
+                    memos.remove(&self.key);
+                }
+            })
+            .ok();
+        self.done.notify_waiters();
+    }
+}
+
+/// Run `f` with the request-scoped value of type `T` (created on first use). Returns `None` if
+/// called outside of a request.
+pub(crate) fn with_local<T, R>(f: impl FnOnce(&mut T) -> R) -> Option<R>
+where
+    T: Default + Send + 'static,
+{
+    SCOPE
+        .try_with(|scope| {
+            let mut locals = scope.locals.lock().unwrap();
+            let local = locals
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| Box::new(T::default()));
+            local.downcast_mut::<T>().map(f)
+        })
         .ok()
         .flatten()
 }
@@ -192,29 +492,45 @@ impl Scope {
             multipart: Default::default(),
             error: Default::default(),
             renderer_pool: Default::default(),
+            memos: Default::default(),
+            after_response: Default::default(),
+            #[cfg(feature = "server-timing")]
+            timings: Timings::new(),
+            locals: Default::default(),
+            nonce: crate::csp::current(),
             is_update,
             disable_hashes,
         }
     }
 
     pub(crate) fn with_event(self, id: String, payload: Payload) -> Self {
-        *(self.event.borrow_mut()) = Some(Event::Raw { id, payload });
+        *(self.event.lock().unwrap()) = Some(Event::Raw { id, payload });
         self
     }
 
     #[cfg(not(target_arch = "wasm32"))]
     pub(crate) fn with_multipart(self, multipart: Multipart<'static>) -> Self {
-        *(self.multipart.borrow_mut()) = Some(multipart);
+        *(self.multipart.lock().unwrap()) = Some(multipart);
         self
     }
 
+    pub(crate) fn after_response(&self) -> AfterResponse {
+        self.after_response.clone()
+    }
+
+    #[cfg(feature = "server-timing")]
+    pub(crate) fn timings(&self) -> Timings {
+        self.timings.clone()
+    }
+
     pub fn is_update(&self) -> bool {
         self.is_update
     }
 
     pub(crate) fn create_renderer(&self) -> Renderer {
         self.renderer_pool
-            .borrow_mut()
+            .lock()
+            .unwrap()
             .pop()
             .unwrap_or_else(|| Renderer::new(self.is_update, self.disable_hashes))
     }
@@ -227,7 +543,7 @@ impl Scope {
 
     pub(crate) fn release_renderer(&self, mut r: Renderer) {
         r.reset();
-        self.renderer_pool.borrow_mut().push(r);
+        self.renderer_pool.lock().unwrap().push(r);
     }
 
     pub(crate) fn release_renderer_to_task(r: Renderer) {
@@ -239,10 +555,10 @@ impl Scope {
         f: impl Future<Output = Result<T, crate::Error>> + Send,
     ) -> Result<T, crate::Error> {
         SCOPE
-            .scope(self, async {
+            .scope(Arc::new(self), async {
                 let t = f.await?;
                 SCOPE.with(|s| {
-                    if let Some(err) = s.error.borrow_mut().take() {
+                    if let Some(err) = s.error.lock().unwrap().take() {
                         return Err(err);
                     }
                     Ok(())
@@ -252,3 +568,108 @@ impl Scope {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memo() {
+        let calls = AtomicUsize::new(0);
+        let load = || async {
+            calls.fetch_add(1, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            42
+        };
+        let results = Scope::new(false, false)
+            .run(async {
+                let mut results =
+                    futures_util::future::join_all((0..3).map(|_| memo("answer", load))).await;
+                results.push(memo("answer", load).await);
+                Ok(results)
+            })
+            .await
+            .unwrap();
+        assert_eq!(results, [42, 42, 42, 42]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // a new request runs it again
+        Scope::new(false, false)
+            .run(async { Ok(memo("answer", load).await) })
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_memo_multi_thread() {
+        for _ in 0..20 {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let results = Scope::new(false, false)
+                .run({
+                    let calls = calls.clone();
+                    async move {
+                        let tasks = (0..8).map(|_| {
+                            let calls = calls.clone();
+                            spawn(async move {
+                                memo("answer", || async move {
+                                    calls.fetch_add(1, Ordering::SeqCst);
+                                    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                                    42
+                                })
+                                .await
+                            })
+                        });
+                        let results = futures_util::future::join_all(tasks).await;
+                        Ok(results.into_iter().map(Result::unwrap).collect::<Vec<_>>())
+                    }
+                })
+                .await
+                .unwrap();
+            assert_eq!(results, [42; 8]);
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawn() {
+        let payload = RawValue::from_string("3".to_string()).unwrap();
+        let (spawned, blocking) = Scope::new(false, false)
+            .with_event("usize".to_string(), Payload::Json(payload))
+            .run(async {
+                let spawned = spawn(async { event::<usize>() }).await.unwrap();
+                let blocking = spawn_blocking(take_event::<usize>).await.unwrap();
+                Ok((spawned, blocking))
+            })
+            .await
+            .unwrap();
+        assert_eq!(spawned, Some(3));
+        assert_eq!(blocking, Some(3));
+
+        assert_eq!(spawn(async { event::<usize>() }).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_after_response() {
+        let (tx, mut rx) = tokio::sync::oneshot::channel();
+        let scope = Scope::new(false, false);
+        let hooks = scope.after_response();
+        scope
+            .run(async {
+                after_response(async move {
+                    tx.send(42).ok();
+                    Ok::<_, std::io::Error>(())
+                });
+                Ok(())
+            })
+            .await
+            .unwrap();
+        tokio::task::yield_now().await;
+        assert!(rx.try_recv().is_err());
+
+        hooks.spawn();
+        assert_eq!(rx.await, Ok(42));
+    }
+}
@@ -4,15 +4,18 @@ use std::sync::LazyLock;
 use bytes::Bytes;
 use futures_util::stream::TryStreamExt;
 pub use http::StatusCode;
-use http::{HeaderValue, Request, Response};
+use http::{HeaderMap, HeaderName, HeaderValue, Request, Response};
 use http_body::Body;
 use http_body_util::BodyExt;
 use http_error::HttpError;
 use mime::Mime;
 use multer::Multipart;
 use serde_json::value::RawValue;
+use tracing::Instrument;
 
+use crate::csp::ScopeNonce;
 pub use crate::error::Error;
+use crate::html::attributes::WithAttribute;
 use crate::render::Out;
 use crate::scope::{Payload, Scope};
 pub use crate::view::View;
@@ -22,6 +25,10 @@ pub static CABIN_JS: &str = include_str!("./cabin.js");
 pub static LIVERELOAD_JS: &str = include_str!("./livereload.js");
 
 pub fn cabin_scripts() -> impl View {
+    scripts(cfg!(feature = "livereload"))
+}
+
+pub(crate) fn scripts(livereload: bool) -> impl View {
     use crate::prelude::*;
 
     let scripts = h::script("")
@@ -33,24 +40,54 @@ pub fn cabin_scripts() -> impl View {
             *PATH
         })
         .defer()
+        .data("cabin-build", build_id())
+        .with_attribute(ScopeNonce)
         .into_any_view();
 
-    #[cfg(feature = "livereload")]
-    let scripts = scripts.appended(
-        h::script("")
-            .src({
-                static PATH: LazyLock<&'static str> = LazyLock::new(|| {
-                    let hash = content_hash(LIVERELOAD_JS.as_bytes());
-                    Box::leak(format!("/livereload.js?{hash}").into_boxed_str())
-                });
-                *PATH
-            })
-            .defer(),
-    );
+    if livereload {
+        scripts.appended(
+            h::script("")
+                .src({
+                    static PATH: LazyLock<&'static str> = LazyLock::new(|| {
+                        let hash = content_hash(LIVERELOAD_JS.as_bytes());
+                        Box::leak(format!("/livereload.js?{hash}").into_boxed_str())
+                    });
+                    *PATH
+                })
+                .defer()
+                .with_attribute(ScopeNonce),
+        )
+    } else {
+        scripts
+    }
+}
+
+/// Identifies the current build. Sent with every update, so that clients running code of another
+/// build (e.g. long-lived tabs after a deploy) reload the page. Set via the `CABIN_BUILD_ID`
+/// environment variable (e.g. to the git commit hash), and defaults to a hash of `cabin.js`.
+pub fn build_id() -> &'static str {
+    static BUILD_ID: LazyLock<String> =
+        LazyLock::new(|| build_id_from(std::env::var("CABIN_BUILD_ID").ok()));
+    &BUILD_ID
+}
 
-    scripts
+fn build_id_from(env: Option<String>) -> String {
+    env.filter(|id| !id.is_empty() && HeaderValue::from_str(id).is_ok())
+        .unwrap_or_else(|| format!("{:x}", content_hash(CABIN_JS.as_bytes())))
+}
+
+/// Add the [build_id] to an update response. Must be set on all of them (including errors), as
+/// e.g. a boundary removed by a deploy results in an error the client would otherwise not
+/// recover from.
+pub(crate) fn with_build_id(mut res: Response<String>) -> Response<String> {
+    if let Ok(build_id) = HeaderValue::from_str(build_id()) {
+        res.headers_mut().insert(BUILD_HEADER_NAME, build_id);
+    }
+    res
 }
 
+const BUILD_HEADER_NAME: HeaderName = HeaderName::from_static("cabin-build");
+
 pub fn content_hash(bytes: &[u8]) -> u32 {
     twox_hash::XxHash32::oneshot(0, bytes)
 }
@@ -62,6 +99,8 @@ pub struct Event {
     pub(crate) multipart: Option<Multipart<'static>>,
 }
 
+/// A minimal document around the `content`. Use [crate::document] for a configurable document
+/// (e.g. with `<meta charset>` and viewport).
 pub fn basic_document(content: impl View) -> AnyView {
     use crate::prelude::*;
 
@@ -83,33 +122,45 @@ where
 {
     let scope = Scope::new(false, false);
     let r = scope.create_renderer();
+    let after_response = scope.after_response();
+    #[cfg(feature = "server-timing")]
+    let timings = scope.timings();
     let result = scope
         // Explicitly put future on heap (Box) to prevent stack overflow for very large futures.
         .run(Box::pin(async move {
             let doc = render_fn().await;
             doc.render(r).await
         }))
+        .instrument(tracing::info_span!("render_page"))
         .await;
-    let Out { html, headers } = match result.and_then(|r| r.end()) {
+    #[allow(unused_mut)]
+    let Out { html, mut headers } = match result.and_then(|r| r.end()) {
         Ok(result) => result,
         Err(err) => return err_to_response(err),
     };
-    let mut res = Response::builder().header(
-        http::header::CONTENT_TYPE,
-        HeaderValue::from_static("text/html; charset=utf-8"),
-    );
-    for (key, value) in headers {
-        if let Some(key) = key {
-            res = res.header(key, value);
-        }
-    }
-    res.body(html).unwrap()
+    #[cfg(feature = "server-timing")]
+    timings.insert_header(&mut headers, html.len());
+    after_response.spawn();
+    html_response(html, headers)
 }
 
 pub async fn put_page<F, V, B>(
     req: Request<B>,
     render_fn: impl FnOnce() -> F + Send + 'static,
 ) -> Response<String>
+where
+    F: Future<Output = V> + Send,
+    V: View,
+    B: Body<Data = Bytes> + Send + 'static,
+    B::Error: std::error::Error + Send + 'static,
+{
+    with_build_id(render_update(req, render_fn).await)
+}
+
+async fn render_update<F, V, B>(
+    req: Request<B>,
+    render_fn: impl FnOnce() -> F + Send + 'static,
+) -> Response<String>
 where
     F: Future<Output = V> + Send,
     V: View,
@@ -120,29 +171,40 @@ where
         Ok(result) => result,
         Err(err) => return err_to_response(err),
     };
+    let span = tracing::info_span!("render_page", event_id = event.event_id);
     let mut scope = Scope::new(true, false).with_event(event.event_id, event.payload);
     if let Some(multipart) = event.multipart {
         scope = scope.with_multipart(multipart);
     }
     let r = scope.create_renderer();
+    let after_response = scope.after_response();
+    #[cfg(feature = "server-timing")]
+    let timings = scope.timings();
     let result = scope
         // Explicitly put future on heap (Box) to prevent stack overflow for very large futures.
         .run(Box::pin(async move { render_fn().await.render(r).await }))
+        .instrument(span)
         .await;
-    let Out { html, headers } = match result.and_then(|r| r.end()) {
+    #[allow(unused_mut)]
+    let Out { html, mut headers } = match result.and_then(|r| r.end()) {
         Ok(result) => result,
         Err(err) => return err_to_response(err),
     };
-    let mut res = Response::builder().header(
+    #[cfg(feature = "server-timing")]
+    timings.insert_header(&mut headers, html.len());
+    after_response.spawn();
+    html_response(html, headers)
+}
+
+pub(crate) fn html_response(html: String, headers: HeaderMap<HeaderValue>) -> Response<String> {
+    let mut res = Response::new(html);
+    res.headers_mut().insert(
         http::header::CONTENT_TYPE,
         HeaderValue::from_static("text/html; charset=utf-8"),
     );
-    for (key, value) in headers {
-        if let Some(key) = key {
-            res = res.header(key, value);
-        }
-    }
-    res.body(html).unwrap()
+    // Keep repeated headers (e.g. multiple client commands)
+    res.headers_mut().extend(headers);
+    res
 }
 
 pub fn err_to_response(err: Error) -> Response<String> {
@@ -180,6 +242,7 @@ pub fn err_to_response(err: Error) -> Response<String> {
     Response::from(err)
 }
 
+#[tracing::instrument(level = "debug", name = "parse_event", skip_all)]
 pub async fn parse_body<B>(req: Request<B>) -> Result<Event, Error>
 where
     B: Body<Data = Bytes> + Send + 'static,
@@ -379,6 +442,51 @@ mod tests {
     use serde::Deserialize;
     use serde_json::value::RawValue;
 
+    use super::*;
+    use crate::render::Renderer;
+
+    #[test]
+    fn test_build_id() {
+        let default = build_id_from(None);
+        assert_eq!(default, format!("{:x}", content_hash(CABIN_JS.as_bytes())));
+        assert_eq!(build_id_from(Some("abc123".to_string())), "abc123");
+        assert_eq!(build_id_from(Some(String::new())), default);
+        assert_eq!(build_id_from(Some("in\nvalid".to_string())), default);
+    }
+
+    #[tokio::test]
+    async fn test_build_id_attribute() {
+        let html = scripts(false)
+            .render(Renderer::new(false, true))
+            .await
+            .unwrap()
+            .end()
+            .unwrap()
+            .html;
+        assert!(
+            html.contains(&format!(r#"data-cabin-build="{}""#, build_id())),
+            "{html}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_id_header() {
+        let req = |body: &str| {
+            Request::builder()
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(body.to_string())
+                .unwrap()
+        };
+        let res = put_page(req(r#"{"eventId":"()","payload":null}"#), || async { "ok" }).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(res.headers()["cabin-build"], build_id());
+
+        // also for errors
+        let res = put_page(req("invalid"), || async { "ok" }).await;
+        assert!(!res.status().is_success());
+        assert_eq!(res.headers()["cabin-build"], build_id());
+    }
+
     #[derive(Debug, Deserialize)]
     struct JsonEvent {
         #[serde(default, deserialize_with = "super::de::deserialize")]
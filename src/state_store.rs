@@ -0,0 +1,258 @@
+//! Server-side storage of boundary state. By default, the state of a boundary is serialized into
+//! the page and sent back with every event. Boundaries opting into server-side state
+//! (`#[cabin::boundary(server_state)]`) instead store their state in a [StateStore] and only
+//! embed an opaque token into the page.
+//!
+//! States are not kept forever. Events of a boundary whose state expired cause the client to
+//! refresh the whole page. States are bound to the boundary they were stored for. States replaced
+//! by an update are not removed right away, as the page might still reference them (e.g. if the
+//! client aborted the request of the update, or for a duplicated tab), but expire like any other
+//! state.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::future::Future;
+use std::hash::{BuildHasher, RandomState};
+use std::pin::Pin;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, crate::Error>> + Send + 'a>>;
+
+pub trait StateStore: Send + Sync + 'static {
+    /// Store the `state` JSON of the given `boundary` and return a token to retrieve it later.
+    fn store<'a>(&'a self, boundary: &'a str, state: String) -> StoreFuture<'a, String>;
+
+    /// Retrieve the state JSON of the `token`, or `None` if it is unknown, expired, or was stored
+    /// for another boundary.
+    fn load<'a>(&'a self, boundary: &'a str, token: &'a str) -> StoreFuture<'a, Option<String>>;
+}
+
+static STORE: OnceLock<Box<dyn StateStore>> = OnceLock::new();
+
+/// Set the store used for boundaries with server-side state. Must be called before the first
+/// such boundary is rendered, and returns `false` if a store was already set (or the default
+/// [MemoryStateStore] was already initialized).
+pub fn set_state_store(store: impl StateStore) -> bool {
+    STORE.set(Box::new(store)).is_ok()
+}
+
+pub(crate) fn state_store() -> &'static dyn StateStore {
+    STORE
+        .get_or_init(|| Box::new(MemoryStateStore::default()))
+        .as_ref()
+}
+
+/// In-memory [StateStore] that evicts the least recently used states once its capacity (number
+/// of states or their total size) is reached, and states that haven't been used for the given
+/// time to live. Storing a state that is already stored for the same boundary returns the
+/// existing token instead of storing it again.
+///
+/// The capacity is shared by all clients. Every render of a boundary with server-side state
+/// (including full page loads, e.g. by crawlers) stores its state, so a client rendering many
+/// distinct states can evict the states of other clients, which then have to refresh their page.
+/// Size the store accordingly, or use a [StateStore] scoped per session for untrusted traffic.
+pub struct MemoryStateStore {
+    capacity: usize,
+    max_bytes: usize,
+    ttl: Duration,
+    hasher: RandomState,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    /// Tokens ordered by their last use.
+    lru: BTreeMap<u64, String>,
+    /// Tokens by the hash of their boundary and state, to not store the same state twice.
+    by_state: HashMap<u64, String>,
+    /// Total size of all stored states.
+    bytes: usize,
+    tick: u64,
+}
+
+struct Entry {
+    boundary: String,
+    state: String,
+    state_hash: u64,
+    expires_at: Instant,
+    tick: u64,
+}
+
+impl MemoryStateStore {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            max_bytes: usize::MAX,
+            ttl,
+            hasher: RandomState::new(),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Limit the total size (in bytes) of all stored states.
+    pub fn with_max_bytes(self, max_bytes: usize) -> Self {
+        Self { max_bytes, ..self }
+    }
+}
+
+impl Default for MemoryStateStore {
+    /// Up to 10,000 states with a total of 64 MiB, each kept for 30 minutes since its last use.
+    fn default() -> Self {
+        Self::new(10_000, Duration::from_secs(30 * 60)).with_max_bytes(64 * 1024 * 1024)
+    }
+}
+
+impl Inner {
+    /// Mark the entry of the `token` as used, or remove it if it expired.
+    fn touch(&mut self, token: &str, ttl: Duration) -> Option<&Entry> {
+        self.tick += 1;
+        let tick = self.tick;
+        let entry = self.entries.get_mut(token)?;
+        let now = Instant::now();
+        if entry.expires_at <= now {
+            self.remove(token);
+            return None;
+        }
+
+        let previous_tick = std::mem::replace(&mut entry.tick, tick);
+        entry.expires_at = now + ttl;
+        self.lru.remove(&previous_tick);
+        self.lru.insert(tick, token.to_string());
+        self.entries.get(token)
+    }
+
+    fn remove(&mut self, token: &str) {
+        let Some(entry) = self.entries.remove(token) else {
+            return;
+        };
+        self.lru.remove(&entry.tick);
+        self.bytes -= entry.state.len();
+        if self.by_state.get(&entry.state_hash).map(String::as_str) == Some(token) {
+            self.by_state.remove(&entry.state_hash);
+        }
+    }
+}
+
+impl StateStore for MemoryStateStore {
+    fn store<'a>(&'a self, boundary: &'a str, state: String) -> StoreFuture<'a, String> {
+        Box::pin(async move {
+            let state_hash = self.hasher.hash_one((boundary, &state));
+            let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+            if let Some(token) = inner.by_state.get(&state_hash).cloned()
+                && inner
+                    .touch(&token, self.ttl)
+                    .is_some_and(|entry| entry.boundary == boundary && entry.state == state)
+            {
+                return Ok(token);
+            }
+
+            let token = generate_token()?;
+            while !inner.entries.is_empty()
+                && (inner.entries.len() >= self.capacity
+                    || inner.bytes.saturating_add(state.len()) > self.max_bytes)
+            {
+                let Some(token) = inner.lru.first_key_value().map(|(_, token)| token.clone())
+                else {
+                    break;
+                };
+                inner.remove(&token);
+            }
+
+            inner.tick += 1;
+            let tick = inner.tick;
+            inner.lru.insert(tick, token.clone());
+            inner.by_state.insert(state_hash, token.clone());
+            inner.bytes += state.len();
+            inner.entries.insert(
+                token.clone(),
+                Entry {
+                    boundary: boundary.to_string(),
+                    state,
+                    state_hash,
+                    expires_at: Instant::now() + self.ttl,
+                    tick,
+                },
+            );
+            Ok(token)
+        })
+    }
+
+    fn load<'a>(&'a self, boundary: &'a str, token: &'a str) -> StoreFuture<'a, Option<String>> {
+        Box::pin(async move {
+            let mut inner = self.inner.lock().unwrap_or_else(|err| err.into_inner());
+            if inner
+                .entries
+                .get(token)
+                .is_none_or(|entry| entry.boundary != boundary)
+            {
+                return Ok(None);
+            }
+            Ok(inner
+                .touch(token, self.ttl)
+                .map(|entry| entry.state.clone()))
+        })
+    }
+}
+
+/// Random token (128 bits, hex encoded) that cannot be guessed to access other users' states.
+fn generate_token() -> Result<String, crate::Error> {
+    let mut bytes = [0u8; 16];
+    getrandom::fill(&mut bytes).map_err(crate::Error::from_err)?;
+    let mut token = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(&mut token, "{b:02x}").unwrap();
+    }
+    Ok(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_state_store() {
+        let store = MemoryStateStore::new(2, Duration::from_secs(60));
+        let a = store.store("x", "1".to_string()).await.unwrap();
+        let b = store.store("x", "2".to_string()).await.unwrap();
+        assert_ne!(a, b);
+
+        // use `a` so that `b` is the least recently used one
+        assert_eq!(store.load("x", &a).await.unwrap().as_deref(), Some("1"));
+        let c = store.store("x", "3".to_string()).await.unwrap();
+        assert_eq!(store.load("x", &b).await.unwrap(), None);
+        assert_eq!(store.load("x", &a).await.unwrap().as_deref(), Some("1"));
+        assert_eq!(store.load("x", &c).await.unwrap().as_deref(), Some("3"));
+        assert_eq!(store.load("x", "unknown").await.unwrap(), None);
+
+        // bound to the boundary
+        assert_eq!(store.load("y", &a).await.unwrap(), None);
+
+        let store = MemoryStateStore::new(2, Duration::ZERO);
+        let a = store.store("x", "1".to_string()).await.unwrap();
+        assert_eq!(store.load("x", &a).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_memory_state_store_dedup() {
+        let store = MemoryStateStore::new(2, Duration::from_secs(60));
+        let a = store.store("x", "1".to_string()).await.unwrap();
+        assert_eq!(store.store("x", "1".to_string()).await.unwrap(), a);
+        let b = store.store("y", "1".to_string()).await.unwrap();
+        assert_ne!(a, b);
+        assert_eq!(store.inner.lock().unwrap().entries.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_memory_state_store_max_bytes() {
+        let store = MemoryStateStore::new(10, Duration::from_secs(60)).with_max_bytes(4);
+        let a = store.store("x", "12".to_string()).await.unwrap();
+        let b = store.store("x", "34".to_string()).await.unwrap();
+        let c = store.store("x", "56".to_string()).await.unwrap();
+        assert_eq!(store.load("x", &a).await.unwrap(), None);
+        assert_eq!(store.load("x", &b).await.unwrap().as_deref(), Some("34"));
+        assert_eq!(store.load("x", &c).await.unwrap().as_deref(), Some("56"));
+        assert_eq!(store.inner.lock().unwrap().bytes, 4);
+    }
+}
@@ -6,7 +6,7 @@ mod property_display;
 mod style_definition;
 mod sub_style;
 mod theme;
-mod units;
+pub mod units;
 
 pub use class_name::ClassName;
 pub use modifier::StyleModifier;
@@ -2082,6 +2082,76 @@ pub trait Style: Sized {
         self
     }
 
+    /// Set the color used to paint the interior of SVG shapes.
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/fill>
+    /// ```css
+    /// fill: {color};` <b style="color:{color}">⏺</b>
+    /// ```
+    fn fill(mut self, color: &'static str) -> Self {
+        self.style_mut().fill = Some(color);
+        self
+    }
+
+    /// ```css
+    /// fill: none;
+    /// ```
+    fn fill_none(mut self) -> Self {
+        self.style_mut().fill = Some("none");
+        self
+    }
+
+    /// ```css
+    /// fill: currentColor;
+    /// ```
+    fn fill_current(mut self) -> Self {
+        self.style_mut().fill = Some("currentColor");
+        self
+    }
+
+    /// Set the color used to paint the outline of SVG shapes.
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/stroke>
+    /// ```css
+    /// stroke: {color};` <b style="color:{color}">⏺</b>
+    /// ```
+    fn stroke(mut self, color: &'static str) -> Self {
+        self.style_mut().stroke = Some(color);
+        self
+    }
+
+    /// ```css
+    /// stroke: none;
+    /// ```
+    fn stroke_none(mut self) -> Self {
+        self.style_mut().stroke = Some("none");
+        self
+    }
+
+    /// ```css
+    /// stroke: currentColor;
+    /// ```
+    fn stroke_current(mut self) -> Self {
+        self.style_mut().stroke = Some("currentColor");
+        self
+    }
+
+    /// Set the width of the outline of SVG shapes.
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/stroke-width>
+    /// ```css
+    /// stroke-width: {x}px;
+    /// ```
+    fn stroke_width(mut self, x: i16) -> Self {
+        self.style_mut().stroke_width = Some(Length::Px(Float::from(x)));
+        self
+    }
+
+    /// ```css
+    /// stroke-width: {x}px;
+    /// ```
+    fn stroke_widthf(mut self, x: f32) -> Self {
+        self.style_mut().stroke_width = Some(Length::Px(Float::from(x)));
+        self
+    }
+
     /// Set the logical inline end position of a positioned element.
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/inset-inline-end>
     /// ```css
@@ -64,6 +64,7 @@ pub struct StyleDefinition {
     pub display: Option<&'static str>,
     pub divide_x_reversed: bool,
     pub divide_y_reversed: bool,
+    pub fill: Option<&'static str>,
     pub flex_direction: Option<&'static str>,
     pub flex_grow: Option<bool>,
     pub flex_shrink: Option<bool>,
@@ -113,6 +114,8 @@ pub struct StyleDefinition {
     pub position: Option<&'static str>,
     pub space_x_reversed: bool,
     pub space_y_reversed: bool,
+    pub stroke: Option<&'static str>,
+    pub stroke_width: Option<Length>,
     pub text_align: Option<&'static str>,
     pub text_overflow: Option<&'static str>,
     pub text_transform: Option<&'static str>,
@@ -169,6 +172,7 @@ impl fmt::Display for StyleDefinition {
             display,
             divide_x_reversed,
             divide_y_reversed,
+            fill,
             flex_direction,
             flex_grow,
             flex_shrink,
@@ -216,6 +220,8 @@ impl fmt::Display for StyleDefinition {
             position,
             space_x_reversed,
             space_y_reversed,
+            stroke,
+            stroke_width,
             text_align,
             text_overflow,
             text_transform,
@@ -279,6 +285,7 @@ impl fmt::Display for StyleDefinition {
         if line_clamp.is_none() {
             display.fmt_property("display", f)?;
         }
+        fill.fmt_property("fill", f)?;
         flex_direction.fmt_property("flex-direction", f)?;
         flex_grow.fmt_property("flex-grow", f)?;
         flex_shrink.fmt_property("flex-shrink", f)?;
@@ -331,6 +338,8 @@ impl fmt::Display for StyleDefinition {
         place_self.fmt_property("place-self", f)?;
         pointer_events.fmt_property("pointer-events", f)?;
         position.fmt_property("position", f)?;
+        stroke.fmt_property("stroke", f)?;
+        stroke_width.fmt_property("stroke-width", f)?;
         text_align.fmt_property("text-align", f)?;
         text_overflow.fmt_property("text-overflow", f)?;
         text_transform.fmt_property("text-transform", f)?;
@@ -413,6 +422,7 @@ impl StyleDefinition {
             display,
             divide_x_reversed,
             divide_y_reversed,
+            fill,
             flex_direction,
             flex_grow,
             flex_shrink,
@@ -460,6 +470,8 @@ impl StyleDefinition {
             position,
             space_x_reversed,
             space_y_reversed,
+            stroke,
+            stroke_width,
             text_align,
             text_overflow,
             text_transform,
@@ -512,6 +524,7 @@ impl StyleDefinition {
         self.display.merge_from(display);
         self.divide_x_reversed.merge_from(divide_x_reversed);
         self.divide_y_reversed.merge_from(divide_y_reversed);
+        self.fill.merge_from(fill);
         self.flex_direction.merge_from(flex_direction);
         self.flex_grow.merge_from(flex_grow);
         self.flex_shrink.merge_from(flex_shrink);
@@ -559,6 +572,8 @@ impl StyleDefinition {
         self.position.merge_from(position);
         self.space_x_reversed.merge_from(space_x_reversed);
         self.space_y_reversed.merge_from(space_y_reversed);
+        self.stroke.merge_from(stroke);
+        self.stroke_width.merge_from(stroke_width);
         self.text_align.merge_from(text_align);
         self.text_overflow.merge_from(text_overflow);
         self.text_transform.merge_from(text_transform);
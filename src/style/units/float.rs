@@ -64,6 +64,12 @@ impl fmt::Display for Float {
     }
 }
 
+impl fmt::Debug for Float {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
 struct SkipTrailingZeroes<'a> {
     wr: &'a mut dyn fmt::Write,
     trim_zeroes: bool,
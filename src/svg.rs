@@ -0,0 +1,92 @@
+//! Typed SVG elements, to be used like the HTML elements in [crate::html::h].
+//!
+//! Fill and stroke colors are best set via the [crate::style::Style] utilities (e.g.
+//! [crate::style::Style::fill_current], [crate::style::Style::stroke_width]), which also take
+//! precedence over presentation attributes.
+
+pub mod elements;
+
+#[doc(inline)]
+pub use elements::circle::{self as circle, Circle, circle};
+#[doc(inline)]
+pub use elements::clip_path::clip_path;
+#[doc(inline)]
+pub use elements::defs::defs;
+#[doc(inline)]
+pub use elements::ellipse::{self as ellipse, Ellipse, ellipse};
+#[doc(inline)]
+pub use elements::g::g;
+#[doc(inline)]
+pub use elements::line::{self as line, Line, line};
+#[doc(inline)]
+pub use elements::linear_gradient::{self as linear_gradient, LinearGradient, linear_gradient};
+#[doc(inline)]
+pub use elements::mask::mask;
+#[doc(inline)]
+pub use elements::path::{self as path, Path, path};
+#[doc(inline)]
+pub use elements::polygon::{self as polygon, Polygon, polygon};
+#[doc(inline)]
+pub use elements::polyline::{self as polyline, Polyline, polyline};
+#[doc(inline)]
+pub use elements::presentation::Presentation;
+#[doc(inline)]
+pub use elements::radial_gradient::{self as radial_gradient, RadialGradient, radial_gradient};
+#[doc(inline)]
+pub use elements::rect::{self as rect, Rect, rect};
+#[doc(inline)]
+pub use elements::stop::{self as stop, Stop, stop};
+#[doc(inline)]
+pub use elements::svg::{self as svg, Svg, svg};
+#[doc(inline)]
+pub use elements::symbol::{self as symbol, Symbol, symbol};
+#[doc(inline)]
+pub use elements::text::{self as text, Text, text};
+#[doc(inline)]
+pub use elements::title::title;
+#[doc(inline)]
+pub use elements::tspan::tspan;
+#[doc(inline)]
+pub use elements::use_::{self as use_, Use, use_};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::View;
+    use crate::render::Renderer;
+    use crate::style::Style;
+    use crate::svg::elements::presentation::StrokeLinecap;
+
+    async fn render(view: impl View) -> String {
+        view.render(Renderer::new(false, true))
+            .await
+            .unwrap()
+            .end()
+            .unwrap()
+            .html
+    }
+
+    #[tokio::test]
+    async fn test_svg_attributes() {
+        assert_eq!(
+            render(svg(()).view_box(0, 0, 24, 24).width(1.5)).await,
+            r#"<svg width="1.5" viewBox="0 0 24 24"></svg>"#
+        );
+        assert_eq!(
+            render(
+                path()
+                    .d("M4 12l5 5L20 6")
+                    .stroke_linecap(StrokeLinecap::Round)
+            )
+            .await,
+            r#"<path stroke-linecap="round" d="M4 12l5 5L20 6"></path>"#
+        );
+    }
+
+    #[tokio::test]
+    async fn test_svg_style() {
+        let html = render(circle().r(10).fill_none().stroke_current()).await;
+        assert!(html.starts_with(r#"<circle class=""#), "{html}");
+        assert!(html.ends_with(r#"" r="10"></circle>"#), "{html}");
+    }
+}
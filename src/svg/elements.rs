@@ -0,0 +1,89 @@
+pub mod circle;
+pub mod ellipse;
+pub mod line;
+pub mod linear_gradient;
+pub mod path;
+pub mod polygon;
+pub mod polyline;
+pub mod presentation;
+pub mod radial_gradient;
+pub mod rect;
+pub mod stop;
+pub mod svg;
+pub mod symbol;
+pub mod text;
+pub mod tspan;
+pub mod use_;
+
+macro_rules! vanilla_svg_element {
+    ($method_name:ident, $tag:literal, $marker_name:ident, $doc:literal) => {
+        pub mod $method_name {
+            #[allow(unused)]
+            use crate::prelude::*;
+
+            #[doc = $doc]
+            #[crate::view_macro(crate::svg::elements::$method_name)]
+            pub fn $method_name(
+                content: impl $crate::View,
+            ) -> $crate::html::Html<marker::$marker_name, ()> {
+                #[cfg(debug_assertions)]
+                let content = content.boxed();
+                $crate::html::Html::new($tag, (), content)
+            }
+
+            pub mod marker {
+                pub struct $marker_name;
+            }
+
+            impl<A: $crate::html::attributes::Attributes> $crate::html::elements::common::Common
+                for $crate::html::Html<marker::$marker_name, A>
+            {
+            }
+            impl<A: $crate::html::attributes::Attributes> $crate::html::elements::aria::Aria
+                for $crate::html::Html<marker::$marker_name, A>
+            {
+            }
+            impl<A: $crate::html::attributes::Attributes>
+                $crate::svg::elements::presentation::Presentation
+                for $crate::html::Html<marker::$marker_name, A>
+            {
+            }
+        }
+    };
+}
+
+vanilla_svg_element!(
+    clip_path,
+    "clipPath",
+    ClipPath,
+    "The `clipPath` element defines a clipping path, to be referenced via the `clip-path` \
+     property. Everything outside of the path is not drawn."
+);
+vanilla_svg_element!(
+    defs,
+    "defs",
+    Defs,
+    "The `defs` element stores graphical objects (like gradients) that are not rendered directly, \
+     but can be referenced by other elements."
+);
+vanilla_svg_element!(
+    g,
+    "g",
+    G,
+    "The `g` element groups other SVG elements. Transformations and styles applied to the group \
+     are inherited by its children."
+);
+vanilla_svg_element!(
+    mask,
+    "mask",
+    Mask,
+    "The `mask` element defines an alpha mask for compositing the current object into the \
+     background, to be referenced via the `mask` property."
+);
+vanilla_svg_element!(
+    title,
+    "title",
+    Title,
+    "The `title` element provides an accessible, short-text description of its parent SVG element \
+     (usually shown as a tooltip)."
+);
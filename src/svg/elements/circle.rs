@@ -0,0 +1,50 @@
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `circle` element draws a circle based on a center point and a radius.
+pub fn circle() -> Html<marker::Circle, ()> {
+    Html::new("circle", (), ())
+}
+
+pub mod marker {
+    pub struct Circle;
+}
+
+impl<A: Attributes> Circle for Html<marker::Circle, A> {}
+impl<A: Attributes> Common for Html<marker::Circle, A> {}
+impl<A: Attributes> Aria for Html<marker::Circle, A> {}
+impl<A: Attributes> Presentation for Html<marker::Circle, A> {}
+
+/// The `circle` element draws a circle based on a center point and a radius.
+pub trait Circle: WithAttribute {
+    /// The x-axis coordinate of the center.
+    fn cx(self, cx: impl Into<Float>) -> Self::Output<Cx> {
+        self.with_attribute(Cx(cx.into()))
+    }
+
+    /// The y-axis coordinate of the center.
+    fn cy(self, cy: impl Into<Float>) -> Self::Output<Cy> {
+        self.with_attribute(Cy(cy.into()))
+    }
+
+    /// The radius.
+    fn r(self, r: impl Into<Float>) -> Self::Output<R> {
+        self.with_attribute(R(r.into()))
+    }
+}
+
+/// The x-axis coordinate of the center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Cx(pub Float);
+
+/// The y-axis coordinate of the center.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Cy(pub Float);
+
+/// The radius.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct R(pub Float);
@@ -0,0 +1,43 @@
+use super::circle::{Cx, Cy};
+use super::presentation::Presentation;
+use super::rect::{Rx, Ry};
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `ellipse` element draws an ellipse based on a center point and two radii.
+pub fn ellipse() -> Html<marker::Ellipse, ()> {
+    Html::new("ellipse", (), ())
+}
+
+pub mod marker {
+    pub struct Ellipse;
+}
+
+impl<A: Attributes> Ellipse for Html<marker::Ellipse, A> {}
+impl<A: Attributes> Common for Html<marker::Ellipse, A> {}
+impl<A: Attributes> Aria for Html<marker::Ellipse, A> {}
+impl<A: Attributes> Presentation for Html<marker::Ellipse, A> {}
+
+/// The `ellipse` element draws an ellipse based on a center point and two radii.
+pub trait Ellipse: WithAttribute {
+    /// The x-axis coordinate of the center.
+    fn cx(self, cx: impl Into<Float>) -> Self::Output<Cx> {
+        self.with_attribute(Cx(cx.into()))
+    }
+
+    /// The y-axis coordinate of the center.
+    fn cy(self, cy: impl Into<Float>) -> Self::Output<Cy> {
+        self.with_attribute(Cy(cy.into()))
+    }
+
+    /// The radius on the x-axis.
+    fn rx(self, rx: impl Into<Float>) -> Self::Output<Rx> {
+        self.with_attribute(Rx(rx.into()))
+    }
+
+    /// The radius on the y-axis.
+    fn ry(self, ry: impl Into<Float>) -> Self::Output<Ry> {
+        self.with_attribute(Ry(ry.into()))
+    }
+}
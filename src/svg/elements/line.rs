@@ -0,0 +1,59 @@
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `line` element draws a straight line connecting two points.
+pub fn line() -> Html<marker::Line, ()> {
+    Html::new("line", (), ())
+}
+
+pub mod marker {
+    pub struct Line;
+}
+
+impl<A: Attributes> Line for Html<marker::Line, A> {}
+impl<A: Attributes> Common for Html<marker::Line, A> {}
+impl<A: Attributes> Aria for Html<marker::Line, A> {}
+impl<A: Attributes> Presentation for Html<marker::Line, A> {}
+
+/// The `line` element draws a straight line connecting two points.
+pub trait Line: WithAttribute {
+    /// The x-axis coordinate of the start point.
+    fn x1(self, x1: impl Into<Float>) -> Self::Output<X1> {
+        self.with_attribute(X1(x1.into()))
+    }
+
+    /// The y-axis coordinate of the start point.
+    fn y1(self, y1: impl Into<Float>) -> Self::Output<Y1> {
+        self.with_attribute(Y1(y1.into()))
+    }
+
+    /// The x-axis coordinate of the end point.
+    fn x2(self, x2: impl Into<Float>) -> Self::Output<X2> {
+        self.with_attribute(X2(x2.into()))
+    }
+
+    /// The y-axis coordinate of the end point.
+    fn y2(self, y2: impl Into<Float>) -> Self::Output<Y2> {
+        self.with_attribute(Y2(y2.into()))
+    }
+}
+
+/// The x-axis coordinate of the start point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct X1(pub Float);
+
+/// The y-axis coordinate of the start point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Y1(pub Float);
+
+/// The x-axis coordinate of the end point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct X2(pub Float);
+
+/// The y-axis coordinate of the end point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Y2(pub Float);
@@ -0,0 +1,90 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use cabin_macros::Attribute;
+
+use super::line::{X1, X2, Y1, Y2};
+use crate::View;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Common, Html};
+use crate::style::units::float::Float;
+
+/// The `linearGradient` element defines a linear gradient, to be referenced via
+/// `url(#id)` from `fill` or `stroke`. The colors are defined via [super::stop::stop] children.
+#[crate::view_macro(crate::svg::elements::linear_gradient)]
+pub fn linear_gradient(content: impl View) -> Html<marker::LinearGradient, ()> {
+    Html::new("linearGradient", (), content)
+}
+
+pub mod marker {
+    pub struct LinearGradient;
+}
+
+impl<A: Attributes> LinearGradient for Html<marker::LinearGradient, A> {}
+impl<A: Attributes> Common for Html<marker::LinearGradient, A> {}
+
+/// The `linearGradient` element defines a linear gradient, to be referenced via
+/// `url(#id)` from `fill` or `stroke`. The colors are defined via [super::stop::stop] children.
+pub trait LinearGradient: WithAttribute {
+    /// The x-axis coordinate of the start of the gradient vector.
+    fn x1(self, x1: impl Into<Float>) -> Self::Output<X1> {
+        self.with_attribute(X1(x1.into()))
+    }
+
+    /// The y-axis coordinate of the start of the gradient vector.
+    fn y1(self, y1: impl Into<Float>) -> Self::Output<Y1> {
+        self.with_attribute(Y1(y1.into()))
+    }
+
+    /// The x-axis coordinate of the end of the gradient vector.
+    fn x2(self, x2: impl Into<Float>) -> Self::Output<X2> {
+        self.with_attribute(X2(x2.into()))
+    }
+
+    /// The y-axis coordinate of the end of the gradient vector.
+    fn y2(self, y2: impl Into<Float>) -> Self::Output<Y2> {
+        self.with_attribute(Y2(y2.into()))
+    }
+
+    /// Coordinate system of the gradient's attributes.
+    fn gradient_units(self, gradient_units: GradientUnits) -> Self::Output<GradientUnits> {
+        self.with_attribute(gradient_units)
+    }
+
+    /// Additional transformation from the gradient's coordinate system onto the target
+    /// coordinate system.
+    fn gradient_transform(
+        self,
+        gradient_transform: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<GradientTransform> {
+        self.with_attribute(GradientTransform(gradient_transform.into()))
+    }
+}
+
+/// Coordinate system of the gradient's attributes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "gradientUnits")]
+pub enum GradientUnits {
+    /// Coordinates are relative to the bounding box of the element the gradient is applied to
+    /// (`0` to `1`).
+    #[default]
+    ObjectBoundingBox,
+
+    /// Coordinates are in the user coordinate system of the element the gradient is applied to.
+    UserSpaceOnUse,
+}
+
+/// Additional transformation from the gradient's coordinate system onto the target coordinate
+/// system.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "gradientTransform")]
+pub struct GradientTransform(pub Cow<'static, str>);
+
+impl fmt::Display for GradientUnits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GradientUnits::ObjectBoundingBox => "objectBoundingBox",
+            GradientUnits::UserSpaceOnUse => "userSpaceOnUse",
+        })
+    }
+}
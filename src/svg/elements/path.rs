@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `path` element defines an arbitrary shape via a list of path commands.
+pub fn path() -> Html<marker::Path, ()> {
+    Html::new("path", (), ())
+}
+
+pub mod marker {
+    pub struct Path;
+}
+
+impl<A: Attributes> Path for Html<marker::Path, A> {}
+impl<A: Attributes> Common for Html<marker::Path, A> {}
+impl<A: Attributes> Aria for Html<marker::Path, A> {}
+impl<A: Attributes> Presentation for Html<marker::Path, A> {}
+
+/// The `path` element defines an arbitrary shape via a list of path commands.
+pub trait Path: WithAttribute {
+    /// The path commands (e.g. `M4 12l5 5L20 6`).
+    fn d(self, d: impl Into<Cow<'static, str>>) -> Self::Output<D> {
+        self.with_attribute(D(d.into()))
+    }
+
+    /// Total length of the path in user units, used to scale distance calculations (e.g. of
+    /// `stroke-dasharray`).
+    fn path_length(self, path_length: impl Into<Float>) -> Self::Output<PathLength> {
+        self.with_attribute(PathLength(path_length.into()))
+    }
+}
+
+/// The path commands.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+pub struct D(pub Cow<'static, str>);
+
+/// Total length of the path in user units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+#[attribute(name = "pathLength")]
+pub struct PathLength(pub Float);
@@ -0,0 +1,28 @@
+use std::borrow::Cow;
+
+use super::polyline::Points;
+use super::presentation::Presentation;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+
+/// The `polygon` element draws a closed shape consisting of connected straight lines.
+pub fn polygon() -> Html<marker::Polygon, ()> {
+    Html::new("polygon", (), ())
+}
+
+pub mod marker {
+    pub struct Polygon;
+}
+
+impl<A: Attributes> Polygon for Html<marker::Polygon, A> {}
+impl<A: Attributes> Common for Html<marker::Polygon, A> {}
+impl<A: Attributes> Aria for Html<marker::Polygon, A> {}
+impl<A: Attributes> Presentation for Html<marker::Polygon, A> {}
+
+/// The `polygon` element draws a closed shape consisting of connected straight lines.
+pub trait Polygon: WithAttribute {
+    /// The list of points (e.g. `0,10 5,0 10,10`).
+    fn points(self, points: impl Into<Cow<'static, str>>) -> Self::Output<Points> {
+        self.with_attribute(Points(points.into()))
+    }
+}
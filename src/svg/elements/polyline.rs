@@ -0,0 +1,33 @@
+use std::borrow::Cow;
+
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+
+/// The `polyline` element draws connected straight lines (an open shape).
+pub fn polyline() -> Html<marker::Polyline, ()> {
+    Html::new("polyline", (), ())
+}
+
+pub mod marker {
+    pub struct Polyline;
+}
+
+impl<A: Attributes> Polyline for Html<marker::Polyline, A> {}
+impl<A: Attributes> Common for Html<marker::Polyline, A> {}
+impl<A: Attributes> Aria for Html<marker::Polyline, A> {}
+impl<A: Attributes> Presentation for Html<marker::Polyline, A> {}
+
+/// The `polyline` element draws connected straight lines (an open shape).
+pub trait Polyline: WithAttribute {
+    /// The list of points (e.g. `0,10 5,0 10,10`).
+    fn points(self, points: impl Into<Cow<'static, str>>) -> Self::Output<Points> {
+        self.with_attribute(Points(points.into()))
+    }
+}
+
+/// The list of points.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+pub struct Points(pub Cow<'static, str>);
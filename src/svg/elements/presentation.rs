@@ -0,0 +1,155 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use cabin_macros::Attribute;
+
+use crate::html::attributes::WithAttribute;
+
+/// Presentation attributes shared by all SVG elements. Fill and stroke colors as well as the
+/// stroke width are set via the [crate::style::Style] utilities instead.
+pub trait Presentation: WithAttribute {
+    /// List of transformations applied to the element and its children (e.g.
+    /// `rotate(45 12 12)`).
+    fn transform(self, transform: impl Into<Cow<'static, str>>) -> Self::Output<Transform> {
+        self.with_attribute(Transform(transform.into()))
+    }
+
+    /// Algorithm used to determine the inside of a shape.
+    fn fill_rule(self, fill_rule: FillRule) -> Self::Output<FillRule> {
+        self.with_attribute(fill_rule)
+    }
+
+    /// Algorithm used to determine the inside of a shape that is part of a clipping path.
+    fn clip_rule(self, clip_rule: FillRule) -> Self::Output<ClipRule> {
+        self.with_attribute(ClipRule(clip_rule))
+    }
+
+    /// Shape at the end of open subpaths when they are stroked.
+    fn stroke_linecap(self, stroke_linecap: StrokeLinecap) -> Self::Output<StrokeLinecap> {
+        self.with_attribute(stroke_linecap)
+    }
+
+    /// Shape at the corners of paths when they are stroked.
+    fn stroke_linejoin(self, stroke_linejoin: StrokeLinejoin) -> Self::Output<StrokeLinejoin> {
+        self.with_attribute(stroke_linejoin)
+    }
+
+    /// Pattern of dashes and gaps used to stroke paths (e.g. `4 2`).
+    fn stroke_dasharray(
+        self,
+        stroke_dasharray: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<StrokeDasharray> {
+        self.with_attribute(StrokeDasharray(stroke_dasharray.into()))
+    }
+
+    /// Keep the stroke width unaffected by transformations (like scaling the `viewBox`).
+    fn non_scaling_stroke(self) -> Self::Output<VectorEffect> {
+        self.with_attribute(VectorEffect::NonScalingStroke)
+    }
+}
+
+/// List of transformations applied to the element and its children.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+pub struct Transform(pub Cow<'static, str>);
+
+/// Algorithm used to determine the inside of a shape.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "fill-rule")]
+pub enum FillRule {
+    /// Inside is determined by the winding direction of the path segments.
+    #[default]
+    NonZero,
+
+    /// Inside is determined by the number of path segments crossed by a ray.
+    EvenOdd,
+}
+
+/// Algorithm used to determine the inside of a shape that is part of a clipping path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "clip-rule")]
+pub struct ClipRule(pub FillRule);
+
+/// Shape at the end of open subpaths when they are stroked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "stroke-linecap")]
+pub enum StrokeLinecap {
+    /// The stroke ends exactly at the end of the path.
+    #[default]
+    Butt,
+
+    /// The stroke is extended by a half circle.
+    Round,
+
+    /// The stroke is extended by a half square.
+    Square,
+}
+
+/// Shape at the corners of paths when they are stroked.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "stroke-linejoin")]
+pub enum StrokeLinejoin {
+    /// Sharp corners.
+    #[default]
+    Miter,
+
+    /// Rounded corners.
+    Round,
+
+    /// Cut-off corners.
+    Bevel,
+}
+
+/// Pattern of dashes and gaps used to stroke paths.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "stroke-dasharray")]
+pub struct StrokeDasharray(pub Cow<'static, str>);
+
+/// Vector effect to use when drawing an element.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "vector-effect")]
+pub enum VectorEffect {
+    /// No special effect.
+    #[default]
+    None,
+
+    /// The stroke width is not affected by transformations.
+    NonScalingStroke,
+}
+
+impl fmt::Display for FillRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            FillRule::NonZero => "nonzero",
+            FillRule::EvenOdd => "evenodd",
+        })
+    }
+}
+
+impl fmt::Display for StrokeLinecap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StrokeLinecap::Butt => "butt",
+            StrokeLinecap::Round => "round",
+            StrokeLinecap::Square => "square",
+        })
+    }
+}
+
+impl fmt::Display for StrokeLinejoin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            StrokeLinejoin::Miter => "miter",
+            StrokeLinejoin::Round => "round",
+            StrokeLinejoin::Bevel => "bevel",
+        })
+    }
+}
+
+impl fmt::Display for VectorEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VectorEffect::None => "none",
+            VectorEffect::NonScalingStroke => "non-scaling-stroke",
+        })
+    }
+}
@@ -0,0 +1,75 @@
+use std::borrow::Cow;
+
+use cabin_macros::Attribute;
+
+use super::circle::{Cx, Cy, R};
+use super::linear_gradient::{GradientTransform, GradientUnits};
+use crate::View;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Common, Html};
+use crate::style::units::float::Float;
+
+/// The `radialGradient` element defines a radial gradient, to be referenced via `url(#id)` from
+/// `fill` or `stroke`. The colors are defined via [super::stop::stop] children.
+#[crate::view_macro(crate::svg::elements::radial_gradient)]
+pub fn radial_gradient(content: impl View) -> Html<marker::RadialGradient, ()> {
+    Html::new("radialGradient", (), content)
+}
+
+pub mod marker {
+    pub struct RadialGradient;
+}
+
+impl<A: Attributes> RadialGradient for Html<marker::RadialGradient, A> {}
+impl<A: Attributes> Common for Html<marker::RadialGradient, A> {}
+
+/// The `radialGradient` element defines a radial gradient, to be referenced via `url(#id)` from
+/// `fill` or `stroke`. The colors are defined via [super::stop::stop] children.
+pub trait RadialGradient: WithAttribute {
+    /// The x-axis coordinate of the end circle.
+    fn cx(self, cx: impl Into<Float>) -> Self::Output<Cx> {
+        self.with_attribute(Cx(cx.into()))
+    }
+
+    /// The y-axis coordinate of the end circle.
+    fn cy(self, cy: impl Into<Float>) -> Self::Output<Cy> {
+        self.with_attribute(Cy(cy.into()))
+    }
+
+    /// The radius of the end circle.
+    fn r(self, r: impl Into<Float>) -> Self::Output<R> {
+        self.with_attribute(R(r.into()))
+    }
+
+    /// The x-axis coordinate of the start circle.
+    fn fx(self, fx: impl Into<Float>) -> Self::Output<Fx> {
+        self.with_attribute(Fx(fx.into()))
+    }
+
+    /// The y-axis coordinate of the start circle.
+    fn fy(self, fy: impl Into<Float>) -> Self::Output<Fy> {
+        self.with_attribute(Fy(fy.into()))
+    }
+
+    /// Coordinate system of the gradient's attributes.
+    fn gradient_units(self, gradient_units: GradientUnits) -> Self::Output<GradientUnits> {
+        self.with_attribute(gradient_units)
+    }
+
+    /// Additional transformation from the gradient's coordinate system onto the target
+    /// coordinate system.
+    fn gradient_transform(
+        self,
+        gradient_transform: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<GradientTransform> {
+        self.with_attribute(GradientTransform(gradient_transform.into()))
+    }
+}
+
+/// The x-axis coordinate of the start circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Fx(pub Float);
+
+/// The y-axis coordinate of the start circle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Fy(pub Float);
@@ -0,0 +1,70 @@
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use super::svg::{Height, Width};
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `rect` element draws a rectangle, optionally with rounded corners.
+pub fn rect() -> Html<marker::Rect, ()> {
+    Html::new("rect", (), ())
+}
+
+pub mod marker {
+    pub struct Rect;
+}
+
+impl<A: Attributes> Rect for Html<marker::Rect, A> {}
+impl<A: Attributes> Common for Html<marker::Rect, A> {}
+impl<A: Attributes> Aria for Html<marker::Rect, A> {}
+impl<A: Attributes> Presentation for Html<marker::Rect, A> {}
+
+/// The `rect` element draws a rectangle, optionally with rounded corners.
+pub trait Rect: WithAttribute {
+    /// The x-axis coordinate of the top left corner.
+    fn x(self, x: impl Into<Float>) -> Self::Output<X> {
+        self.with_attribute(X(x.into()))
+    }
+
+    /// The y-axis coordinate of the top left corner.
+    fn y(self, y: impl Into<Float>) -> Self::Output<Y> {
+        self.with_attribute(Y(y.into()))
+    }
+
+    /// The width of the rectangle.
+    fn width(self, width: impl Into<Float>) -> Self::Output<Width> {
+        self.with_attribute(Width(width.into()))
+    }
+
+    /// The height of the rectangle.
+    fn height(self, height: impl Into<Float>) -> Self::Output<Height> {
+        self.with_attribute(Height(height.into()))
+    }
+
+    /// The horizontal corner radius.
+    fn rx(self, rx: impl Into<Float>) -> Self::Output<Rx> {
+        self.with_attribute(Rx(rx.into()))
+    }
+
+    /// The vertical corner radius.
+    fn ry(self, ry: impl Into<Float>) -> Self::Output<Ry> {
+        self.with_attribute(Ry(ry.into()))
+    }
+}
+
+/// An x-axis coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct X(pub Float);
+
+/// A y-axis coordinate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Y(pub Float);
+
+/// A radius on the x-axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Rx(pub Float);
+
+/// A radius on the y-axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Ry(pub Float);
@@ -0,0 +1,53 @@
+use std::borrow::Cow;
+
+use cabin_macros::Attribute;
+
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Common, Html};
+use crate::style::units::float::Float;
+
+/// The `stop` element defines a color and its position within a
+/// [super::linear_gradient::linear_gradient] or [super::radial_gradient::radial_gradient].
+pub fn stop() -> Html<marker::Stop, ()> {
+    Html::new("stop", (), ())
+}
+
+pub mod marker {
+    pub struct Stop;
+}
+
+impl<A: Attributes> Stop for Html<marker::Stop, A> {}
+impl<A: Attributes> Common for Html<marker::Stop, A> {}
+
+/// The `stop` element defines a color and its position within a
+/// [super::linear_gradient::linear_gradient] or [super::radial_gradient::radial_gradient].
+pub trait Stop: WithAttribute {
+    /// Position along the gradient vector (`0` to `1`).
+    fn offset(self, offset: impl Into<Float>) -> Self::Output<Offset> {
+        self.with_attribute(Offset(offset.into()))
+    }
+
+    /// Color of the stop.
+    fn stop_color(self, stop_color: impl Into<Cow<'static, str>>) -> Self::Output<StopColor> {
+        self.with_attribute(StopColor(stop_color.into()))
+    }
+
+    /// Opacity of the stop (`0` to `1`).
+    fn stop_opacity(self, stop_opacity: impl Into<Float>) -> Self::Output<StopOpacity> {
+        self.with_attribute(StopOpacity(stop_opacity.into()))
+    }
+}
+
+/// Position along the gradient vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Offset(pub Float);
+
+/// Color of the stop.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "stop-color")]
+pub struct StopColor(pub Cow<'static, str>);
+
+/// Opacity of the stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+#[attribute(name = "stop-opacity")]
+pub struct StopOpacity(pub Float);
@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::hash::Hash;
+
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use crate::View;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `svg` element is a container that defines a new coordinate system and viewport. It is
+/// used as the outermost element of SVG documents, and to embed SVG graphics into HTML.
+#[crate::view_macro(crate::svg::elements::svg)]
+pub fn svg(content: impl View) -> Html<marker::Svg, ()> {
+    Html::new("svg", (), content)
+}
+
+pub mod marker {
+    pub struct Svg;
+}
+
+impl<A: Attributes> Svg for Html<marker::Svg, A> {}
+impl<A: Attributes> Common for Html<marker::Svg, A> {}
+impl<A: Attributes> Aria for Html<marker::Svg, A> {}
+impl<A: Attributes> Presentation for Html<marker::Svg, A> {}
+
+/// The `svg` element is a container that defines a new coordinate system and viewport. It is
+/// used as the outermost element of SVG documents, and to embed SVG graphics into HTML.
+pub trait Svg: WithAttribute {
+    /// Position and dimension of the viewport in user space (e.g. `view_box(0, 0, 24, 24)`).
+    fn view_box(
+        self,
+        min_x: impl Into<Float>,
+        min_y: impl Into<Float>,
+        width: impl Into<Float>,
+        height: impl Into<Float>,
+    ) -> Self::Output<ViewBox> {
+        self.with_attribute(ViewBox {
+            min_x: min_x.into(),
+            min_y: min_y.into(),
+            width: width.into(),
+            height: height.into(),
+        })
+    }
+
+    /// Displayed width of the viewport.
+    fn width(self, width: impl Into<Float>) -> Self::Output<Width> {
+        self.with_attribute(Width(width.into()))
+    }
+
+    /// Displayed height of the viewport.
+    fn height(self, height: impl Into<Float>) -> Self::Output<Height> {
+        self.with_attribute(Height(height.into()))
+    }
+
+    /// How the graphic is fitted into a viewport with a different aspect ratio (e.g.
+    /// `xMidYMid slice`).
+    fn preserve_aspect_ratio(
+        self,
+        preserve_aspect_ratio: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<PreserveAspectRatio> {
+        self.with_attribute(PreserveAspectRatio(preserve_aspect_ratio.into()))
+    }
+}
+
+/// Position and dimension of the viewport in user space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+#[attribute(outer, name = "viewBox")]
+pub struct ViewBox {
+    pub min_x: Float,
+    pub min_y: Float,
+    pub width: Float,
+    pub height: Float,
+}
+
+/// Displayed width of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Width(pub Float);
+
+/// Displayed height of the element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Height(pub Float);
+
+/// How the graphic is fitted into a viewport with a different aspect ratio.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "preserveAspectRatio")]
+pub struct PreserveAspectRatio(pub Cow<'static, str>);
+
+impl fmt::Display for ViewBox {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.min_x, self.min_y, self.width, self.height
+        )
+    }
+}
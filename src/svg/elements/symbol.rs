@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+
+use super::presentation::Presentation;
+use super::svg::{PreserveAspectRatio, ViewBox};
+use crate::View;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `symbol` element defines a graphical template that is not rendered directly, but can be
+/// instantiated (multiple times) via [super::use_::use_].
+#[crate::view_macro(crate::svg::elements::symbol)]
+pub fn symbol(content: impl View) -> Html<marker::Symbol, ()> {
+    Html::new("symbol", (), content)
+}
+
+pub mod marker {
+    pub struct Symbol;
+}
+
+impl<A: Attributes> Symbol for Html<marker::Symbol, A> {}
+impl<A: Attributes> Common for Html<marker::Symbol, A> {}
+impl<A: Attributes> Aria for Html<marker::Symbol, A> {}
+impl<A: Attributes> Presentation for Html<marker::Symbol, A> {}
+
+/// The `symbol` element defines a graphical template that is not rendered directly, but can be
+/// instantiated (multiple times) via [super::use_::use_].
+pub trait Symbol: WithAttribute {
+    /// Position and dimension of the viewport in user space.
+    fn view_box(
+        self,
+        min_x: impl Into<Float>,
+        min_y: impl Into<Float>,
+        width: impl Into<Float>,
+        height: impl Into<Float>,
+    ) -> Self::Output<ViewBox> {
+        self.with_attribute(ViewBox {
+            min_x: min_x.into(),
+            min_y: min_y.into(),
+            width: width.into(),
+            height: height.into(),
+        })
+    }
+
+    /// How the graphic is fitted into a viewport with a different aspect ratio.
+    fn preserve_aspect_ratio(
+        self,
+        preserve_aspect_ratio: impl Into<Cow<'static, str>>,
+    ) -> Self::Output<PreserveAspectRatio> {
+        self.with_attribute(PreserveAspectRatio(preserve_aspect_ratio.into()))
+    }
+}
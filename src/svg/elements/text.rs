@@ -0,0 +1,127 @@
+use std::fmt;
+
+use cabin_macros::Attribute;
+
+use super::presentation::Presentation;
+use super::rect::{X, Y};
+use crate::View;
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `text` element draws a graphics element consisting of text.
+#[crate::view_macro(crate::svg::elements::text)]
+pub fn text(content: impl View) -> Html<marker::Text, ()> {
+    Html::new("text", (), content)
+}
+
+pub mod marker {
+    pub struct Text;
+}
+
+impl<A: Attributes> Text for Html<marker::Text, A> {}
+impl<A: Attributes> Common for Html<marker::Text, A> {}
+impl<A: Attributes> Aria for Html<marker::Text, A> {}
+impl<A: Attributes> Presentation for Html<marker::Text, A> {}
+
+/// The `text` element draws a graphics element consisting of text.
+pub trait Text: WithAttribute {
+    /// The x-axis coordinate of the start of the text baseline.
+    fn x(self, x: impl Into<Float>) -> Self::Output<X> {
+        self.with_attribute(X(x.into()))
+    }
+
+    /// The y-axis coordinate of the start of the text baseline.
+    fn y(self, y: impl Into<Float>) -> Self::Output<Y> {
+        self.with_attribute(Y(y.into()))
+    }
+
+    /// Shift along the x-axis relative to the previous text position.
+    fn dx(self, dx: impl Into<Float>) -> Self::Output<Dx> {
+        self.with_attribute(Dx(dx.into()))
+    }
+
+    /// Shift along the y-axis relative to the previous text position.
+    fn dy(self, dy: impl Into<Float>) -> Self::Output<Dy> {
+        self.with_attribute(Dy(dy.into()))
+    }
+
+    /// Alignment of the text relative to its start point.
+    fn text_anchor(self, text_anchor: TextAnchor) -> Self::Output<TextAnchor> {
+        self.with_attribute(text_anchor)
+    }
+
+    /// Baseline used to align the text vertically.
+    fn dominant_baseline(
+        self,
+        dominant_baseline: DominantBaseline,
+    ) -> Self::Output<DominantBaseline> {
+        self.with_attribute(dominant_baseline)
+    }
+}
+
+/// Shift along the x-axis relative to the previous text position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Dx(pub Float);
+
+/// Shift along the y-axis relative to the previous text position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Attribute)]
+pub struct Dy(pub Float);
+
+/// Alignment of the text relative to its start point.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "text-anchor")]
+pub enum TextAnchor {
+    /// The text starts at the start point.
+    #[default]
+    Start,
+
+    /// The text is centered around the start point.
+    Middle,
+
+    /// The text ends at the start point.
+    End,
+}
+
+/// Baseline used to align the text vertically.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Attribute)]
+#[attribute(name = "dominant-baseline")]
+pub enum DominantBaseline {
+    /// Determined by the user agent based on the writing mode.
+    #[default]
+    Auto,
+
+    /// The alphabetic baseline.
+    Alphabetic,
+
+    /// The center of the em box.
+    Middle,
+
+    /// The central baseline (centered between ideographic top and bottom).
+    Central,
+
+    /// The top of the em box.
+    Hanging,
+}
+
+impl fmt::Display for TextAnchor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TextAnchor::Start => "start",
+            TextAnchor::Middle => "middle",
+            TextAnchor::End => "end",
+        })
+    }
+}
+
+impl fmt::Display for DominantBaseline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DominantBaseline::Auto => "auto",
+            DominantBaseline::Alphabetic => "alphabetic",
+            DominantBaseline::Middle => "middle",
+            DominantBaseline::Central => "central",
+            DominantBaseline::Hanging => "hanging",
+        })
+    }
+}
@@ -0,0 +1,21 @@
+use super::presentation::Presentation;
+use super::text::Text;
+use crate::View;
+use crate::html::attributes::Attributes;
+use crate::html::{Aria, Common, Html};
+
+/// The `tspan` element defines a subtext within a [super::text::text] element, which can be
+/// positioned and styled individually.
+#[crate::view_macro(crate::svg::elements::tspan)]
+pub fn tspan(content: impl View) -> Html<marker::TSpan, ()> {
+    Html::new("tspan", (), content)
+}
+
+pub mod marker {
+    pub struct TSpan;
+}
+
+impl<A: Attributes> Text for Html<marker::TSpan, A> {}
+impl<A: Attributes> Common for Html<marker::TSpan, A> {}
+impl<A: Attributes> Aria for Html<marker::TSpan, A> {}
+impl<A: Attributes> Presentation for Html<marker::TSpan, A> {}
@@ -0,0 +1,53 @@
+use std::borrow::Cow;
+
+use super::presentation::Presentation;
+use super::rect::{X, Y};
+use super::svg::{Height, Width};
+use crate::html::attributes::{Attributes, WithAttribute};
+use crate::html::elements::anchor::Href;
+use crate::html::{Aria, Common, Html};
+use crate::style::units::float::Float;
+
+/// The `use` element renders a copy of another element (usually a [super::symbol::symbol]),
+/// referenced via its `href`.
+pub fn use_() -> Html<marker::Use, ()> {
+    Html::new("use", (), ())
+}
+
+pub mod marker {
+    pub struct Use;
+}
+
+impl<A: Attributes> Use for Html<marker::Use, A> {}
+impl<A: Attributes> Common for Html<marker::Use, A> {}
+impl<A: Attributes> Aria for Html<marker::Use, A> {}
+impl<A: Attributes> Presentation for Html<marker::Use, A> {}
+
+/// The `use` element renders a copy of another element (usually a [super::symbol::symbol]),
+/// referenced via its `href`.
+pub trait Use: WithAttribute {
+    /// Reference to the element to copy (e.g. `#icon-check` or `/icons.svg#check`).
+    fn href(self, href: impl Into<Cow<'static, str>>) -> Self::Output<Href> {
+        self.with_attribute(Href(href.into()))
+    }
+
+    /// The x-axis coordinate of the copy.
+    fn x(self, x: impl Into<Float>) -> Self::Output<X> {
+        self.with_attribute(X(x.into()))
+    }
+
+    /// The y-axis coordinate of the copy.
+    fn y(self, y: impl Into<Float>) -> Self::Output<Y> {
+        self.with_attribute(Y(y.into()))
+    }
+
+    /// The width of the copy (only has an effect for referenced `svg` and `symbol` elements).
+    fn width(self, width: impl Into<Float>) -> Self::Output<Width> {
+        self.with_attribute(Width(width.into()))
+    }
+
+    /// The height of the copy (only has an effect for referenced `svg` and `symbol` elements).
+    fn height(self, height: impl Into<Float>) -> Self::Output<Height> {
+        self.with_attribute(Height(height.into()))
+    }
+}
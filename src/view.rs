@@ -1,10 +1,12 @@
 mod any;
 pub mod boundary;
 mod boxed;
+mod concurrent;
 pub mod error;
 mod future;
 mod iter;
 mod macros;
+mod out_of_band;
 pub mod text;
 mod update;
 
@@ -17,10 +19,12 @@ use std::task::{Context, Poll};
 pub use any::AnyView;
 pub use boundary::Boundary;
 pub use boxed::BoxedView;
+pub use concurrent::Concurrently;
 pub use future::FutureExt;
 use http_error::HttpError;
-pub use iter::IteratorExt;
+pub use iter::{IteratorExt, Reorder};
 pub use macros::view;
+pub use out_of_band::{OutOfBand, out_of_band};
 pub use update::UpdateView;
 
 pub use crate::pair::Pair;
@@ -1,9 +1,12 @@
 use smallvec::SmallVec;
 
+use crate::csp::ScopeNonce;
 use crate::html::Common;
+use crate::html::attributes::WithAttribute;
 use crate::render::Renderer;
 use crate::scope::Scope;
 use crate::view::RenderFuture;
+use crate::view::concurrent::Concurrently;
 use crate::{View, h};
 
 pub struct AnyView {
@@ -23,15 +26,18 @@ impl AnyView {
         let r = Scope::create_renderer_from_task();
         match self.render(r).await {
             Ok(mut r) => {
-                let css = r.build_styles(is_page_style);
+                let css = crate::scope::build_styles(|| r.build_styles(is_page_style));
                 (
                     Self {
                         views: smallvec::smallvec![RenderFuture::Ready(Ok(r))],
                     },
                     if is_page_style {
-                        h::style(css).id("cabin-styles").boxed()
+                        h::style(css)
+                            .id("cabin-styles")
+                            .with_attribute(ScopeNonce)
+                            .boxed()
                     } else {
-                        h::style(css).boxed()
+                        h::style(css).with_attribute(ScopeNonce).boxed()
                     },
                 )
             }
@@ -44,6 +50,12 @@ impl AnyView {
         }
     }
 
+    /// Render the views (e.g. of a `view![]`) concurrently in separate tasks (at most `limit` at
+    /// the same time, or the number of CPUs for a `limit` of `0`). Useful for CPU-heavy views.
+    pub fn render_concurrently(self, limit: usize) -> Concurrently {
+        Concurrently::new(self.views.into_iter(), limit)
+    }
+
     pub fn appended(mut self, other: impl View) -> Self {
         let Some(last) = self.views.last_mut() else {
             return self;
@@ -9,8 +9,9 @@ use script::Script;
 use serde::Serialize;
 
 use super::RenderFuture;
+use crate::csp::ScopeNonce;
 use crate::error::InternalError;
-use crate::html::attributes::Attributes;
+use crate::html::attributes::{Attributes, WithAttribute};
 use crate::html::{Html, script};
 use crate::render::{ElementRenderer, Renderer};
 use crate::view::error::ErrorView;
@@ -28,6 +29,78 @@ where
     events: &'static [&'static str],
     args: PhantomData<Args>,
     f: &'static BoundaryFn<Args>,
+    version: u32,
+    migrate: Option<MigrateFn<Args>>,
+    server_state: bool,
+}
+
+/// Migrates the state JSON of an older version (the first argument) of a boundary.
+pub type MigrateFn<Args> = fn(u32, &str) -> Option<Args>;
+
+/// A state transition of a reducer-style boundary, declared via
+/// `#[cabin::boundary(reduce(EventA, EventB))]`. The boundary's single argument is its state, and
+/// it must implement [Reducer] for each of the declared events. The event (if any) is applied to
+/// the state before the boundary's body is executed.
+///
+/// Declared events without a [Reducer] implementation fail to compile, and so does reading
+/// undeclared events within the boundary's body via `event::<E>()` or `take_event::<E>()` (unless
+/// called via their full path, e.g. `cabin::scope::event::<E>()`).
+///
+/// ```rust
+/// # use cabin::prelude::*;
+/// # use cabin::view::boundary::{Boundary, Reducer};
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Clone, Copy, Serialize, Deserialize)]
+/// # struct Counter(u32);
+/// # #[derive(Clone, Copy, cabin::Event, Serialize, Deserialize)]
+/// # struct Increment(u32);
+/// impl Reducer<Increment> for Counter {
+///     fn update(self, Increment(by): Increment) -> Self {
+///         Counter(self.0 + by)
+///     }
+/// }
+///
+/// #[cabin::boundary(reduce(Increment))]
+/// fn counter(state: Counter) -> Boundary<Counter> {
+///     h::button(h::text!("{}", state.0))
+///         .on_click(Increment(1))
+///         .boundary(state)
+/// }
+/// # cabin::BOUNDARIES!();
+/// # fn main() {}
+/// ```
+///
+/// Reading an undeclared event fails to compile:
+///
+/// ```rust,compile_fail
+/// # use cabin::prelude::*;
+/// # use cabin::scope::event;
+/// # use cabin::view::boundary::{Boundary, Reducer};
+/// # use serde::{Deserialize, Serialize};
+/// # #[derive(Clone, Copy, Serialize, Deserialize)]
+/// # struct Counter(u32);
+/// # #[derive(Clone, Copy, cabin::Event, Serialize, Deserialize)]
+/// # struct Increment(u32);
+/// # #[derive(Clone, Copy, cabin::Event, Serialize, Deserialize)]
+/// # struct Reset;
+/// # impl Reducer<Increment> for Counter {
+/// #     fn update(self, Increment(by): Increment) -> Self {
+/// #         Counter(self.0 + by)
+/// #     }
+/// # }
+/// #[cabin::boundary(reduce(Increment))]
+/// fn counter(state: Counter) -> Boundary<Counter> {
+///     // error: `Reset` is not declared in `reduce(..)` of this boundary
+///     let state = if event::<Reset>().is_some() { Counter(0) } else { state };
+///     h::button(h::text!("{}", state.0))
+///         .on_click(Increment(1))
+///         .boundary(state)
+/// }
+/// # cabin::BOUNDARIES!();
+/// # fn main() {}
+/// ```
+pub trait Reducer<E>: Sized {
+    fn update(self, event: E) -> Self;
 }
 
 #[derive(Default)]
@@ -49,9 +122,52 @@ where
             events,
             args: PhantomData,
             f,
+            version: 0,
+            migrate: None,
+            server_state: false,
+        }
+    }
+
+    /// Set the schema version of the boundary's state (`0` by default). Bump it whenever the
+    /// boundary's arguments change incompatibly. Events of pages rendered with another version
+    /// are either migrated (see [BoundaryRef::with_migration]) or cause the client to refresh the
+    /// whole page.
+    pub const fn with_version(self, version: u32) -> Self {
+        Self { version, ..self }
+    }
+
+    /// Set a function to migrate the state of an older version of the boundary. Returning
+    /// `None` causes the client to refresh the whole page.
+    pub const fn with_migration(self, migrate: MigrateFn<Args>) -> Self {
+        Self {
+            migrate: Some(migrate),
+            ..self
         }
     }
 
+    /// Keep the boundary's state on the server (see [crate::state_store]) and only embed an opaque
+    /// token into the page. Useful for large states, which would otherwise bloat the page and be
+    /// sent back with every event. Not supported for wasm boundaries.
+    pub const fn with_server_state(self) -> Self {
+        Self {
+            server_state: true,
+            ..self
+        }
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn has_server_state(&self) -> bool {
+        self.server_state
+    }
+
+    pub(crate) fn migrate(&self, version: u32, state_json: &str) -> Option<Args> {
+        self.migrate
+            .and_then(|migrate| migrate(version, state_json))
+    }
+
     pub async fn with(&'static self, args: Args) -> Boundary<Args> {
         self::internal::Boundary::upgrade((self.f)(args).await, self).into_topmost()
     }
@@ -158,6 +274,20 @@ where
     view: AnyView,
     // Used to collect styles into top most boundary on updates
     is_topmost: bool,
+    concurrency: Concurrency,
+}
+
+/// How a boundary handles an event while the update of a previous event is still in flight.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Concurrency {
+    /// Abort the in-flight update in favor of the new event (e.g. for search-as-you-type).
+    #[default]
+    AbortPrevious,
+    /// Handle the new event once the in-flight update finished (e.g. for counters, where each
+    /// event matters).
+    Queue,
+    /// Ignore the new event (e.g. to prevent double submits).
+    DropNew,
 }
 
 impl<Args> Boundary<Args>
@@ -170,8 +300,16 @@ where
             args: Some(args),
             view: view.into_any_view(),
             is_topmost: false,
+            concurrency: Concurrency::default(),
         }
     }
+
+    /// Set how the boundary handles events while an update is still in flight (defaults to
+    /// [Concurrency::AbortPrevious]).
+    pub fn concurrency(mut self, concurrency: Concurrency) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
 }
 
 pub mod internal {
@@ -205,6 +343,19 @@ pub mod internal {
             self
         }
     }
+
+    /// Implemented by `#[cabin::boundary(reduce(..))]` for each declared event, with `B` being a
+    /// marker type of the boundary. Reading any other event within the boundary's body via
+    /// `event::<E>()` or `take_event::<E>()` fails to compile.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` is not declared in `reduce(..)` of this boundary",
+        label = "undeclared event",
+        note = "add the event to `#[cabin::boundary(reduce(..))]`"
+    )]
+    pub trait DeclaredEvent<B>:
+        serde::de::DeserializeOwned + crate::event::Event + Send + 'static
+    {
+    }
 }
 
 impl<Args> Boundary<Args>
@@ -256,7 +407,14 @@ where
             #[allow(clippy::async_yields_async)]
             async move {
                 let (view, styles) = self.view.collect_styles(false).await;
-                crate::view![styles, h::script(state).r#type("application/json"), view]
+                crate::view![
+                    styles,
+                    StateScript {
+                        state,
+                        server_state: boundary_ref.server_state.then_some(boundary_ref.id),
+                    },
+                    view
+                ]
             }
             .into_any_view()
             .render(r)
@@ -264,13 +422,53 @@ where
             Html::<(), _>::new(
                 "cabin-boundary",
                 boundary_ref,
-                crate::view![h::script(state).r#type("application/json"), self.view],
+                crate::view![
+                    StateScript {
+                        state,
+                        server_state: boundary_ref.server_state.then_some(boundary_ref.id),
+                    },
+                    self.view
+                ],
             )
+            .with_attribute(self.concurrency)
             .render(r)
         }
     }
 }
 
+/// The boundary's state JSON, or (for boundaries with server-side state) the JSON string of the
+/// token the state got stored under.
+struct StateScript {
+    state: String,
+    /// The id of the boundary, if its state is stored server-side.
+    server_state: Option<&'static str>,
+}
+
+impl View for StateScript {
+    fn render(self, r: Renderer) -> RenderFuture {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(boundary) = self.server_state {
+            return RenderFuture::Future(Box::pin(async move {
+                let token = crate::state_store::state_store()
+                    .store(boundary, self.state)
+                    .await?;
+                StateScript {
+                    // tokens are hex encoded and thus don't need any escaping
+                    state: format!("\"{token}\""),
+                    server_state: None,
+                }
+                .render(r)
+                .await
+            }));
+        }
+
+        h::script(self.state)
+            .r#type("application/json")
+            .with_attribute(ScopeNonce)
+            .render(r)
+    }
+}
+
 impl<Args> Attributes for &'static BoundaryRef<Args>
 where
     Args: Send + Sync + 'static,
@@ -278,6 +476,20 @@ where
     fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
         r.attribute("name", self.id);
         r.attribute("events", EventsList(self.events));
+        if self.version != 0 {
+            r.attribute("version", self.version);
+        }
+        Ok(())
+    }
+}
+
+impl Attributes for Concurrency {
+    fn render(self, r: &mut ElementRenderer) -> Result<(), crate::Error> {
+        match self {
+            Concurrency::AbortPrevious => {}
+            Concurrency::Queue => r.attribute("concurrency", "queue"),
+            Concurrency::DropNew => r.attribute("concurrency", "drop"),
+        }
         Ok(())
     }
 }
@@ -312,7 +524,54 @@ where
                 args: None,
                 view: View::into_any_view(Err::<Boundary<Args>, _>(err)),
                 is_topmost: false,
+                concurrency: Concurrency::default(),
             },
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::internal::Boundary as _;
+    use super::*;
+    use crate::scope::Scope;
+
+    static BOUNDARY: BoundaryRef<u32> = BoundaryRef::new(
+        "counter",
+        &["Increment"],
+        &(|count: u32| Box::pin(async move { Boundary::new(count.to_string(), count) })),
+    );
+
+    /// The boundary's opening tag.
+    async fn render(concurrency: Concurrency) -> String {
+        let scope = Scope::new(false, true);
+        let r = scope.create_renderer();
+        let boundary = Boundary::new("1", 1u32)
+            .upgrade(&BOUNDARY)
+            .concurrency(concurrency);
+        let html = scope
+            .run(boundary.render(r))
+            .await
+            .unwrap()
+            .end()
+            .unwrap()
+            .html;
+        html[..=html.find('>').unwrap()].to_string()
+    }
+
+    #[tokio::test]
+    async fn test_concurrency() {
+        assert_eq!(
+            render(Concurrency::AbortPrevious).await,
+            r#"<cabin-boundary name="counter" events="Increment">"#
+        );
+        assert_eq!(
+            render(Concurrency::Queue).await,
+            r#"<cabin-boundary concurrency="queue" name="counter" events="Increment">"#
+        );
+        assert_eq!(
+            render(Concurrency::DropNew).await,
+            r#"<cabin-boundary concurrency="drop" name="counter" events="Increment">"#
+        );
+    }
+}
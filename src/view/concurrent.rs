@@ -0,0 +1,171 @@
+use futures_util::stream::{self, StreamExt};
+
+use super::RenderFuture;
+pub use super::View;
+use crate::render::Renderer;
+
+/// Renders its children concurrently, each in its own task (see [crate::scope::spawn]), and
+/// appends them in order once done. Created via [super::IteratorExt::render_concurrently] and
+/// [super::AnyView::render_concurrently].
+pub struct Concurrently {
+    children: Box<dyn Iterator<Item = RenderFuture> + Send>,
+    limit: usize,
+}
+
+impl Concurrently {
+    /// Render at most `limit` of the `children` at the same time. A `limit` of `0` defaults to
+    /// the number of CPUs.
+    pub(crate) fn new(
+        children: impl Iterator<Item = RenderFuture> + Send + 'static,
+        limit: usize,
+    ) -> Self {
+        Self {
+            children: Box::new(children),
+            limit: if limit == 0 { num_cpus::get() } else { limit },
+        }
+    }
+}
+
+impl View for Concurrently {
+    fn render(self, mut r: Renderer) -> RenderFuture {
+        RenderFuture::Future(Box::pin(async move {
+            let mut children = stream::iter(self.children)
+                .map(|child| async move {
+                    match child {
+                        RenderFuture::Ready(result) => result,
+                        RenderFuture::Future(future) => {
+                            match AbortOnDrop(crate::scope::spawn(future)).wait().await {
+                                Ok(result) => result,
+                                Err(err) if err.is_panic() => {
+                                    std::panic::resume_unwind(err.into_panic())
+                                }
+                                Err(err) => Err(crate::Error::from_err(err)),
+                            }
+                        }
+                    }
+                })
+                .buffered(self.limit);
+            while let Some(child) = children.next().await {
+                r.append(child?);
+            }
+            Ok(r)
+        }))
+    }
+}
+
+/// Aborts the spawned task once dropped, so that children don't keep running (and doing IO) once
+/// their parent's render was dropped (e.g. for an aborted update).
+struct AbortOnDrop<T>(tokio::task::JoinHandle<T>);
+
+impl<T> AbortOnDrop<T> {
+    async fn wait(mut self) -> Result<T, tokio::task::JoinError> {
+        (&mut self.0).await
+    }
+}
+
+impl<T> Drop for AbortOnDrop<T> {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+    use crate::scope::Scope;
+    use crate::view::{FutureExt, IteratorExt};
+
+    #[tokio::test]
+    async fn test_render_concurrently() {
+        let scope = Scope::new(false, true);
+        let r = scope.create_renderer();
+        let list = scope
+            .run((0..4u64).map(h_li).render_concurrently(2).render(r))
+            .await
+            .unwrap()
+            .end()
+            .unwrap();
+        assert_eq!(list.html, "<li>0</li><li>1</li><li>2</li><li>3</li>");
+
+        let scope = Scope::new(false, true);
+        let r = scope.create_renderer();
+        let any = scope
+            .run(async {
+                crate::view![h_li(3), h_li(1), h_li(2)]
+                    .render_concurrently(0)
+                    .render(r)
+                    .await
+            })
+            .await
+            .unwrap()
+            .end()
+            .unwrap();
+        assert_eq!(any.html, "<li>3</li><li>1</li><li>2</li>");
+    }
+
+    #[tokio::test]
+    async fn test_render_concurrently_hashes() {
+        let render = |concurrently: bool| async move {
+            let scope = Scope::new(false, false);
+            let r = scope.create_renderer();
+            scope
+                .run(async move {
+                    let list = (0..4u64).map(h_li);
+                    let list = if concurrently {
+                        list.render_concurrently(2).into_any_view()
+                    } else {
+                        list.into_any_view()
+                    };
+                    crate::h::ul(list).render(r).await
+                })
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html
+        };
+        let sequential = render(false).await;
+        assert!(sequential.contains("hash="), "{sequential}");
+        assert_eq!(render(true).await, sequential);
+    }
+
+    #[tokio::test]
+    async fn test_render_concurrently_abort() {
+        let finished = Arc::new(AtomicUsize::new(0));
+        let children = (0..4u64).map({
+            let finished = finished.clone();
+            move |_| {
+                let finished = finished.clone();
+                async move {
+                    tokio::time::sleep(Duration::from_millis(50)).await;
+                    finished.fetch_add(1, Ordering::SeqCst);
+                }
+                .into_any_view()
+            }
+        });
+        let scope = Scope::new(false, true);
+        let r = scope.create_renderer();
+        let render = scope.run(children.render_concurrently(0).render(r));
+        // drop the parent render before the children are done
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), render)
+                .await
+                .is_err()
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(finished.load(Ordering::SeqCst), 0);
+    }
+
+    fn h_li(i: u64) -> crate::view::AnyView {
+        async move {
+            // finish in reverse order
+            tokio::time::sleep(Duration::from_millis(10 * (4 - i))).await;
+            crate::h::li(crate::h::text!("{i}"))
+        }
+        .into_any_view()
+    }
+}
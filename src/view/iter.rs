@@ -2,11 +2,17 @@ use std::hash::{Hash, Hasher};
 use std::iter::{FilterMap, Map};
 use std::marker::PhantomData;
 
+use serde::{Deserialize, Serialize};
 use twox_hash::XxHash32;
 
 use super::RenderFuture;
 pub use super::View;
+use crate::error::InternalError;
+use crate::event::Event;
+use crate::html::events::{CustomEvent, DraggedKey, DropKey};
 use crate::render::Renderer;
+use crate::scope::Scope;
+use crate::view::concurrent::Concurrently;
 
 pub trait IteratorExt
 where
@@ -17,6 +23,20 @@ where
         Self: Sized,
         F: FnMut(&Self::Item) -> K,
         K: Hash;
+
+    /// Render the views of the iterator concurrently (at most `limit` at the same time, or the
+    /// number of CPUs for a `limit` of `0`), instead of one after another. Useful if each view
+    /// awaits its own IO.
+    fn render_concurrently(self, limit: usize) -> Concurrently
+    where
+        Self: Sized + Send + 'static,
+        Self::Item: View,
+    {
+        Concurrently::new(
+            self.map(|view| view.render(Scope::create_renderer_from_task().record_hash())),
+            limit,
+        )
+    }
 }
 
 impl<Iter> IteratorExt for Iter
@@ -66,6 +86,23 @@ where
     }
 }
 
+impl<I, F, K> Keyed<I, F, K>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: Hash + Serialize,
+{
+    /// Make the list sortable via drag and drop: dropping an item onto another one fires `event`
+    /// (usually containing a [Reorder::placeholder()]). The items must be marked as draggable
+    /// (see [crate::html::Global::draggable]).
+    pub fn sortable<E>(self, event: E) -> Sortable<I, F, K, E>
+    where
+        E: Serialize + Event + Clone + Send + 'static,
+    {
+        Sortable { keyed: self, event }
+    }
+}
+
 impl<I: Iterator, F, K> Iterator for Keyed<I, F, K>
 where
     F: FnMut(&I::Item) -> K,
@@ -143,3 +180,147 @@ where
         el.content(self.view)
     }
 }
+
+pub struct Sortable<I, F, K, E> {
+    keyed: Keyed<I, F, K>,
+    event: E,
+}
+
+impl<I, F, K, E> Sortable<I, F, K, E>
+where
+    I: Iterator,
+    F: FnMut(&I::Item) -> K,
+    K: Hash + Serialize,
+    E: Serialize + Event + Clone + Send + 'static,
+{
+    pub fn map<B>(
+        self,
+        mut f: impl FnMut(I::Item) -> B,
+    ) -> Map<I, impl FnMut(I::Item) -> SortableView<B, E>> {
+        let Keyed {
+            iter, f: mut key, ..
+        } = self.keyed;
+        let event = self.event;
+        iter.map(move |item| {
+            let key = key(&item);
+            SortableView {
+                key: hash(&key),
+                key_json: serde_json::to_string(&key),
+                event: event.clone(),
+                view: (f)(item),
+            }
+        })
+    }
+}
+
+pub struct SortableView<V, E> {
+    key: u32,
+    key_json: Result<String, serde_json::Error>,
+    event: E,
+    view: V,
+}
+
+impl<V, E> View for SortableView<V, E>
+where
+    V: View,
+    E: Serialize + Event + Send + 'static,
+{
+    fn render(self, r: Renderer) -> RenderFuture {
+        let key_json = match self.key_json {
+            Ok(key_json) => key_json,
+            Err(err) => {
+                return RenderFuture::Ready(Err(InternalError::Serialize {
+                    what: "sortable key".into(),
+                    err,
+                }
+                .into()));
+            }
+        };
+
+        let mut el = r.element("cabin-keyed");
+        el.attribute("id", self.key);
+        el.attribute("cabin-key", key_json);
+        if let Err(err) = el.event_attributes(CustomEvent::new("drop", self.event)) {
+            return RenderFuture::Ready(Err(err));
+        }
+        el.content(self.view)
+    }
+}
+
+/// Event payload of a sortable list (see `Keyed::sortable`): the item with the key `from` was
+/// dropped onto the item with the key `to`. Use [Reorder::placeholder] as part of the sortable's
+/// event, the client replaces it with the actual keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Reorder<K> {
+    pub from: DraggedKey<K>,
+    pub to: DropKey<K>,
+}
+
+impl<K> Reorder<K> {
+    pub fn new(from: K, to: K) -> Self {
+        Reorder {
+            from: DraggedKey::new(Some(from)),
+            to: DropKey::new(Some(to)),
+        }
+    }
+
+    pub fn placeholder() -> Self {
+        Reorder {
+            from: DraggedKey::placeholder(),
+            to: DropKey::placeholder(),
+        }
+    }
+
+    /// Move the item with the key `from` to the current position of the item with the key `to`.
+    /// Does nothing if either of the keys is missing or cannot be found.
+    pub fn apply<T>(&self, items: &mut Vec<T>, mut key: impl FnMut(&T) -> K)
+    where
+        K: PartialEq,
+    {
+        let (Some(from), Some(to)) = (self.from.get(), self.to.get()) else {
+            return;
+        };
+        let Some(from) = items.iter().position(|item| key(item) == *from) else {
+            return;
+        };
+        let Some(to) = items.iter().position(|item| key(item) == *to) else {
+            return;
+        };
+        let item = items.remove(from);
+        items.insert(to, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reorder() {
+        let mut items = vec![1, 2, 3, 4];
+        Reorder::new(1, 3).apply(&mut items, |i| *i);
+        assert_eq!(items, [2, 3, 1, 4]);
+        Reorder::new(4, 2).apply(&mut items, |i| *i);
+        assert_eq!(items, [4, 2, 3, 1]);
+        Reorder::new(5, 2).apply(&mut items, |i| *i);
+        assert_eq!(items, [4, 2, 3, 1]);
+        Reorder::placeholder().apply(&mut items, |i| *i);
+        assert_eq!(items, [4, 2, 3, 1]);
+
+        assert_eq!(
+            serde_json::to_string(&Reorder::<u32>::placeholder()).unwrap(),
+            r#"{"from":"_##DraggedKey","to":"_##DropKey"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&Reorder::new(1, 3)).unwrap(),
+            r#"{"from":1,"to":3}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Reorder<u32>>(r#"{"from":1,"to":null}"#).unwrap(),
+            Reorder {
+                from: DraggedKey::new(Some(1)),
+                to: DropKey::new(None),
+            }
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::borrow::Cow;
+
+use super::RenderFuture;
+use crate::View;
+use crate::render::Renderer;
+
+/// Update the content of the element with the given `id` as part of the response of an event,
+/// even though it is outside of the boundary that handles the event (e.g. a cart badge in the
+/// header after adding a product to the cart). Renders nothing outside of updates.
+pub fn out_of_band<V: View>(id: impl Into<Cow<'static, str>>, view: V) -> OutOfBand<V> {
+    OutOfBand {
+        id: id.into(),
+        view,
+    }
+}
+
+pub struct OutOfBand<V> {
+    id: Cow<'static, str>,
+    view: V,
+}
+
+impl<V: View> View for OutOfBand<V> {
+    fn render(self, r: Renderer) -> RenderFuture {
+        if !r.is_update() {
+            return RenderFuture::Ready(Ok(r));
+        }
+
+        let mut el = r.element("template");
+        el.attribute("cabin-oob", self.id);
+        el.content(self.view)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_out_of_band() {
+        assert_eq!(
+            out_of_band("badge", "3")
+                .render(Renderer::new(false, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            ""
+        );
+        assert_eq!(
+            out_of_band("badge", "3")
+                .render(Renderer::new(true, true))
+                .await
+                .unwrap()
+                .end()
+                .unwrap()
+                .html,
+            r#"<template cabin-oob="badge">3</template>"#
+        );
+    }
+}
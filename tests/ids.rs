@@ -0,0 +1,47 @@
+use cabin::Event;
+use cabin::prelude::*;
+use cabin::scope::event;
+use cabin::view::boundary::Boundary;
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Clone, Copy, Event, Serialize, Deserialize)]
+struct Increment(usize);
+
+#[derive(Default, Clone, Copy, Event, Serialize, Deserialize)]
+struct Decrement(usize);
+
+#[cabin::boundary(Increment, Decrement)]
+fn counter(count: usize) -> Boundary<usize> {
+    let count = event::<Increment>()
+        .map(|e| e.0)
+        .or_else(|| event::<Decrement>().map(|e| e.0))
+        .unwrap_or(count);
+
+    view![
+        h::button("-").on_click(Decrement(count.saturating_sub(1))),
+        h::text!("{}", count),
+        h::button("+").on_click(Increment(count + 1)),
+    ]
+    .boundary(count)
+}
+
+// Also generates the `__boundaries::unique_ids` test.
+cabin::BOUNDARIES!();
+
+#[test]
+fn mapping_table() {
+    let table = cabin::id::mapping_table();
+    for (kind, full_path) in [
+        ("event", "ids::Increment"),
+        ("event", "ids::Decrement"),
+        ("boundary", "ids::counter"),
+    ] {
+        assert!(
+            table.lines().any(|line| {
+                let columns = line.split_whitespace().collect::<Vec<_>>();
+                columns[0] == kind && columns[2] == full_path
+            }),
+            "{table}"
+        );
+    }
+}
@@ -80,3 +80,10 @@ fn animation() {
         });
     insta::assert_snapshot!(c.build());
 }
+
+#[test]
+fn svg_paint() {
+    let c = StyleCollector::default();
+    let c = c.fill_none().stroke_current().stroke_width(2);
+    insta::assert_snapshot!(c.build());
+}